@@ -1,6 +1,8 @@
 
 use std::{env::args_os, fs::File, io::Write};
 
+use cs377_filesystem::myfs;
+
 // create a file  to act as a disk  and format the file system residing on the disk
 
 fn main() -> Result<(), std::io::Error> {
@@ -9,26 +11,14 @@ fn main() -> Result<(), std::io::Error> {
         eprintln!("usage: {:?} <diskFileName> ", args[0].as_os_str());
         return Err(std::io::ErrorKind::InvalidInput.into());
     }
-    println!("Creating a 128KB file in {:?}", args[1]);
+    println!("Creating a {}KB file in {:?}", myfs::DISK_SIZE / 1024, args[1]);
     println!("This file will act as a dummy disk and will hold your filesystem");
 
     let mut my_disk = File::create(&args[1])?;
 
     println!("Formatting your filesystem...");
 
-    let mut buf = [0u8; 1024];
-    // Mark superblock as allocated in the free block list all other blocks
-    // are free, all inodes are zeroed out.
-    buf[0] = 1;
-
-    // Write out the superblock
-    my_disk.write(&buf)?;
-
-    buf[0] = 0;
-
-    for _ in 0..127 {
-        my_disk.write(&buf)?;
-    }
+    my_disk.write_all(&myfs::format_image())?;
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,24 +1,299 @@
 pub mod myfs {
     use std::{
         fs::{File, OpenOptions},
-        io::{Read, Seek, Write},
+        io::{Cursor, Read, Seek, Write},
     };
 
     /*
-    Equivalent to `idxNode` struct in original implementation however
-    to have equivalent byte representation after a std::mem::transmute,
-    it is necessary to define a repr, hence #[repr(C)]. Additionally,
-    the original project has the block_pointers field as an array of ints
-    even though there are only 128 blocks total, so it really should be an
-    array of u8 instead. I have opted to keep the original project format
-    for compatibility.
+    Equivalent to `idxNode` struct in original implementation. Additionally, the
+    original project has the block_pointers field as an array of ints even though
+    there are only 128 blocks total, so it really should be an array of u8 instead.
+    I have opted to keep the original project format for compatibility.
     */
-    #[repr(C)]
+    #[derive(Clone, Copy)]
     struct IDXNode {
         name: [u8; 8],
-        size: u8,
+        size: u32,
         block_pointers: [u32; 8],
         used: u8,
+        mode: u16,
+        uid: u32,
+        gid: u32,
+        atime: u64,
+        mtime: u64,
+        ctime: u64,
+        /// Hard-link count. A regular file is always created with one link; a
+        /// directory is created with two (its own `.` entry, plus the entry its
+        /// parent holds for it). `unlink` decrements this and only frees the inode
+        /// once it reaches zero.
+        links: u16,
+    }
+
+    impl IDXNode {
+        /// Packs the inode into its fixed, little-endian on-disk representation. Writing
+        /// every field out explicitly (instead of transmuting the struct's in-memory
+        /// layout) means the wire format doesn't depend on the host's endianness or on
+        /// whatever padding the compiler happens to insert between fields.
+        fn encode(&self) -> [u8; IDXNODE_SIZE] {
+            let mut buf = [0u8; IDXNODE_SIZE];
+            let mut offset = 0;
+
+            buf[offset..offset + 8].copy_from_slice(&self.name);
+            offset += 8;
+            buf[offset..offset + 4].copy_from_slice(&self.size.to_le_bytes());
+            offset += 4;
+            for pointer in &self.block_pointers {
+                buf[offset..offset + 4].copy_from_slice(&pointer.to_le_bytes());
+                offset += 4;
+            }
+            buf[offset] = self.used;
+            offset += 1;
+            buf[offset..offset + 2].copy_from_slice(&self.mode.to_le_bytes());
+            offset += 2;
+            buf[offset..offset + 4].copy_from_slice(&self.uid.to_le_bytes());
+            offset += 4;
+            buf[offset..offset + 4].copy_from_slice(&self.gid.to_le_bytes());
+            offset += 4;
+            buf[offset..offset + 8].copy_from_slice(&self.atime.to_le_bytes());
+            offset += 8;
+            buf[offset..offset + 8].copy_from_slice(&self.mtime.to_le_bytes());
+            offset += 8;
+            buf[offset..offset + 8].copy_from_slice(&self.ctime.to_le_bytes());
+            offset += 8;
+            buf[offset..offset + 2].copy_from_slice(&self.links.to_le_bytes());
+            offset += 2;
+
+            debug_assert_eq!(offset, IDXNODE_SIZE);
+            buf
+        }
+
+        /// Unpacks an inode from its on-disk little-endian representation, panicking
+        /// if it looks corrupt (per this crate's fail-loud philosophy for unreadable
+        /// on-disk state — see `check_format_version`). The inverse of `encode`.
+        fn decode(buf: [u8; IDXNODE_SIZE]) -> IDXNode {
+            let mut offset = 0;
+
+            let mut name = [0u8; 8];
+            name.copy_from_slice(&buf[offset..offset + 8]);
+            offset += 8;
+            let size = u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap());
+            offset += 4;
+            let mut block_pointers = [0u32; 8];
+            for pointer in &mut block_pointers {
+                *pointer = u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap());
+                offset += 4;
+            }
+            let used = buf[offset];
+            offset += 1;
+            let mode = u16::from_le_bytes(buf[offset..offset + 2].try_into().unwrap());
+            offset += 2;
+            let uid = u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap());
+            offset += 4;
+            let gid = u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap());
+            offset += 4;
+            let atime = u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap());
+            offset += 8;
+            let mtime = u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap());
+            offset += 8;
+            let ctime = u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap());
+            offset += 8;
+            let links = u16::from_le_bytes(buf[offset..offset + 2].try_into().unwrap());
+            offset += 2;
+
+            debug_assert_eq!(offset, IDXNODE_SIZE);
+            let inode = IDXNode {
+                name,
+                size,
+                block_pointers,
+                used,
+                mode,
+                uid,
+                gid,
+                atime,
+                mtime,
+                ctime,
+                links,
+            };
+            inode.validate();
+            inode
+        }
+
+        /// Panics if `used` is anything but 0/1, or if `size` is larger than this
+        /// crate's own encoder would ever produce: a block count past the maximum a
+        /// fully-indirected file/directory can address, or (for a symlink, which
+        /// repurposes `size` as a target byte length) past `BLOCK_SIZE`. Either one
+        /// means the bytes just read off disk aren't a real encoded `IDXNode`.
+        fn validate(&self) {
+            assert!(
+                self.used <= 1,
+                "corrupt inode: used flag {} is neither 0 nor 1",
+                self.used
+            );
+            let max_size = if is_symlink(self) {
+                BLOCK_SIZE
+            } else {
+                DIRECT_POINTERS
+                    + POINTERS_PER_INDIRECT_BLOCK
+                    + POINTERS_PER_INDIRECT_BLOCK * POINTERS_PER_INDIRECT_BLOCK
+            };
+            assert!(
+                self.size as usize <= max_size,
+                "corrupt inode: size {} exceeds maximum of {}",
+                self.size,
+                max_size
+            );
+        }
+    }
+
+    /// One record in a directory's data blocks, mapping a name to the inode it
+    /// refers to. `inode == 0` marks an empty/free slot (real inode numbers, per
+    /// `inode_number`, start at 1), so a zeroed block is a block of empty entries
+    /// with no extra initialization needed.
+    #[derive(Clone, Copy)]
+    struct DirEntry {
+        inode: u32,
+        name_len: u8,
+        name: [u8; MAX_NAME_LEN],
+    }
+
+    impl DirEntry {
+        fn encode(&self) -> [u8; DIRENTRY_SIZE] {
+            let mut buf = [0u8; DIRENTRY_SIZE];
+            buf[0..4].copy_from_slice(&self.inode.to_le_bytes());
+            buf[4] = self.name_len;
+            buf[5..5 + MAX_NAME_LEN].copy_from_slice(&self.name);
+            buf
+        }
+
+        fn decode(buf: &[u8]) -> DirEntry {
+            let inode = u32::from_le_bytes(buf[0..4].try_into().unwrap());
+            let name_len = buf[4];
+            let mut name = [0u8; MAX_NAME_LEN];
+            name.copy_from_slice(&buf[5..5 + MAX_NAME_LEN]);
+            DirEntry {
+                inode,
+                name_len,
+                name,
+            }
+        }
+
+        fn name_str(&self) -> &str {
+            std::str::from_utf8(&self.name[..self.name_len as usize]).unwrap_or("")
+        }
+    }
+
+    /// Packs `name` into a `DirEntry`'s fixed-width name field. Errs if `name` is
+    /// longer than `MAX_NAME_LEN`, the same cap `IDXNode.name` itself has always had.
+    fn encode_name(name: &str) -> Result<([u8; MAX_NAME_LEN], u8), String> {
+        let bytes = name.as_bytes();
+        if bytes.len() > MAX_NAME_LEN {
+            return Err(format!(
+                "name {:?} exceeds the {}-byte name cap",
+                name, MAX_NAME_LEN
+            ));
+        }
+        let mut buf = [0u8; MAX_NAME_LEN];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        Ok((buf, bytes.len() as u8))
+    }
+
+    /// How many bytes of symlink target fit packed directly into `block_pointers`
+    /// (a "fast" symlink). A target up to this long needs no data block at all;
+    /// anything longer spills into one, per `symlink`.
+    const FAST_SYMLINK_MAX_LEN: usize = 8 * 4;
+
+    /// Packs `target`'s bytes into an inode's `block_pointers` array, 4 bytes per
+    /// `u32` slot, for a fast symlink. Only valid when
+    /// `target.len() <= FAST_SYMLINK_MAX_LEN`.
+    fn encode_fast_symlink_target(target: &str) -> [u32; 8] {
+        let mut raw = [0u8; FAST_SYMLINK_MAX_LEN];
+        raw[..target.len()].copy_from_slice(target.as_bytes());
+        let mut pointers = [0u32; 8];
+        for (pointer, chunk) in pointers.iter_mut().zip(raw.chunks_exact(4)) {
+            *pointer = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        pointers
+    }
+
+    /// The inverse of `encode_fast_symlink_target`: unpacks `len` bytes of target
+    /// out of `pointers`.
+    fn decode_fast_symlink_target(pointers: &[u32; 8], len: usize) -> String {
+        let mut raw = [0u8; FAST_SYMLINK_MAX_LEN];
+        for (chunk, pointer) in raw.chunks_exact_mut(4).zip(pointers.iter()) {
+            chunk.copy_from_slice(&pointer.to_le_bytes());
+        }
+        String::from_utf8_lossy(&raw[..len]).into_owned()
+    }
+
+    /// Splits `path` into its parent directory and leaf name, e.g. `/a/b` into
+    /// (`/a`, `b`). The root itself has no leaf to split off, so it is rejected.
+    fn split_path(path: &str) -> Result<(&str, &str), String> {
+        let trimmed = path.trim_end_matches('/');
+        match trimmed.rfind('/') {
+            Some(pos) => {
+                let name = &trimmed[pos + 1..];
+                if name.is_empty() {
+                    return Err(format!("{}: invalid path", path));
+                }
+                let parent = if pos == 0 { "/" } else { &trimmed[..pos] };
+                Ok((parent, name))
+            }
+            None => Err(format!("{}: invalid path", path)),
+        }
+    }
+
+    /// Directory bit within `mode` (the POSIX `S_IFDIR` constant).
+    const S_IFDIR: u16 = 0o040000;
+    /// Symbolic-link bit within `mode` (the POSIX `S_IFLNK` constant).
+    const S_IFLNK: u16 = 0o120000;
+    /// Default Unix mode bits stamped on a newly created file: a regular file (`S_IFREG`)
+    /// readable/writable by owner and readable by group/other.
+    const DEFAULT_FILE_MODE: u16 = 0o100644;
+    /// Default Unix mode bits stamped on a newly created directory: `S_IFDIR`,
+    /// readable/writable/searchable by owner and readable/searchable by group/other.
+    const DEFAULT_DIR_MODE: u16 = S_IFDIR | 0o755;
+    /// Default Unix mode bits stamped on a newly created symlink: `S_IFLNK`, with
+    /// the conventional all-access permission bits (the target's own permissions are
+    /// what's actually enforced once it's followed).
+    const DEFAULT_SYMLINK_MODE: u16 = S_IFLNK | 0o777;
+
+    /// Whether `inode` is a directory, per its `mode` bits.
+    fn is_dir(inode: &IDXNode) -> bool {
+        inode.mode & S_IFDIR == S_IFDIR
+    }
+
+    /// Whether `inode` is a symbolic link, per its `mode` bits.
+    fn is_symlink(inode: &IDXNode) -> bool {
+        inode.mode & S_IFLNK == S_IFLNK
+    }
+
+    /// A POSIX rwx access kind, tested against an inode's owner/group/other mode bits
+    /// by `check_permission`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Access {
+        Read,
+        Write,
+    }
+
+    /// Tests whether `uid`/`gid` may perform `access` on `inode`, per the owner/group/
+    /// other rwx bits packed into its `mode`. `uid == 0` (root) always passes, matching
+    /// POSIX's superuser bypass.
+    fn check_permission(inode: &IDXNode, uid: u32, gid: u32, access: Access) -> bool {
+        if uid == 0 {
+            return true;
+        }
+        let shift = if inode.uid == uid {
+            6
+        } else if inode.gid == gid {
+            3
+        } else {
+            0
+        };
+        let bit: u16 = match access {
+            Access::Read => 0o4,
+            Access::Write => 0o2,
+        };
+        (inode.mode >> shift) & bit != 0
     }
 
     /*
@@ -28,17 +303,306 @@ pub mod myfs {
     functions on the struct mutates the internal state of the struct itself.
     Here, with as faithful as an adaptation as possible to the original, the
     filesystem is a wrapper around a file stream.
+
+    `MyFileSystem` is generic over its storage backend `D: BlockDevice` so that it is
+    not hard-wired to a real `std::fs::File`. Any byte-addressable stream works, which
+    lets the test suite (and, eventually, other embedders) back the filesystem with an
+    in-memory `MemoryDisk` instead of shelling out to `./create_fs` and opening a real
+    file on disk.
     */
-    pub struct MyFileSystem {
-        disk: File,
+    pub struct MyFileSystem<D> {
+        disk: D,
+        /// Write-back cache of decoded inodes, keyed by inode table index. Populated
+        /// lazily on first access and fixed at `MAX_INODES` entries, since the whole
+        /// inode table is small enough to hold resident rather than evicting.
+        inode_cache: [Option<CachedInode>; MAX_INODES],
+        /// Write-back cache of the decoded free-block bitmap. There is only ever one,
+        /// so unlike `inode_cache` this is a single slot rather than a table.
+        free_block_cache: Option<CachedBitmap>,
+    }
+
+    /// A decoded inode plus whether it has been written since the last flush. `dirty`
+    /// entries are the ones `flush` writes back to `disk`.
+    #[derive(Clone, Copy)]
+    struct CachedInode {
+        inode: IDXNode,
+        dirty: bool,
     }
 
+    /// A decoded free-block bitmap plus whether it has been written since the last flush.
+    #[derive(Clone, Copy)]
+    struct CachedBitmap {
+        bitmap: Bitmap,
+        dirty: bool,
+    }
+
+    /// Byte-addressable storage backend for `MyFileSystem`. Blanket-implemented for any
+    /// `Read + Write + Seek`, so a real file, an in-memory buffer, or any other
+    /// seekable stream all work as a `BlockDevice` without extra glue.
+    pub trait BlockDevice: Read + Write + Seek {}
+    impl<T: Read + Write + Seek> BlockDevice for T {}
+
+    /// `MyFileSystem`'s on-disk backend: a real file, opened by `MyFileSystem::new`.
+    pub type FileDisk = File;
+    /// `MyFileSystem`'s in-memory backend, used by the test suite and anywhere else a
+    /// real file isn't available or desired. Created by `MyFileSystem::new_in_memory`.
+    pub type MemoryDisk = Cursor<Vec<u8>>;
+
     pub const BLOCK_SIZE: usize = 1024;
+    /// Size in bytes of the free-list region at the start of the disk. Block addressing
+    /// (`FREE_BLOCK_SIZE + BLOCK_SIZE * block_index`) is anchored on this offset, so it
+    /// stays fixed even though the bitmap packed into it only needs `FREE_LIST_BYTES`;
+    /// the remainder of the region is reserved padding.
     const FREE_BLOCK_SIZE: usize = 128;
     const MAX_INODES: usize = 16;
-    const IDXNODE_SIZE: usize = std::mem::size_of::<IDXNode>();
+    /// Size in bytes of an encoded `IDXNode` on disk: the sum of each field's
+    /// little-endian width, with no implicit struct padding.
+    const IDXNODE_SIZE: usize = 8 + 4 + 4 * 8 + 1 + 2 + 4 + 4 + 8 + 8 + 8 + 2;
+    /// Number of blocks on disk.
+    const NUM_BLOCKS: usize = FREE_BLOCK_SIZE;
+    /// Number of bytes needed to pack one bit per block into a free-block bitmap.
+    const FREE_LIST_BYTES: usize = NUM_BLOCKS.div_ceil(8);
+    /// Total size in bytes of a freshly formatted disk image (see `create_fs`).
+    pub const DISK_SIZE: usize = NUM_BLOCKS * BLOCK_SIZE;
+    /// How many of `block_pointers`'s slots address data blocks directly. The next
+    /// slot is a single indirect pointer, and the last is a double indirect pointer,
+    /// once a file grows past this many blocks.
+    const DIRECT_POINTERS: usize = 6;
+    /// Index of the single indirect pointer in `block_pointers`.
+    const INDIRECT_POINTER_INDEX: usize = 6;
+    /// Index of the double indirect pointer in `block_pointers`.
+    const DOUBLE_INDIRECT_POINTER_INDEX: usize = 7;
+    /// An indirect block is just `BLOCK_SIZE` bytes packed with `u32` block indices.
+    const POINTERS_PER_INDIRECT_BLOCK: usize = BLOCK_SIZE / std::mem::size_of::<u32>();
+
+    /// Inode table index of the root directory. Always present and always a
+    /// directory; `format_image` sets it up and it's never freed.
+    const ROOT_INODE_INDEX: usize = 0;
+    /// Name cap for a directory entry's name, matching `IDXNode.name`'s own 8-byte
+    /// cap so a leaf's name round-trips identically between its own inode and the
+    /// entry its parent directory holds for it.
+    const MAX_NAME_LEN: usize = 8;
+    /// Size in bytes of an encoded `DirEntry`: inode number + name length + name bytes.
+    const DIRENTRY_SIZE: usize = 4 + 1 + MAX_NAME_LEN;
+    /// How many `DirEntry` records fit in one data block.
+    const DIRENTRIES_PER_BLOCK: usize = BLOCK_SIZE / DIRENTRY_SIZE;
+
+    /// Converts an inode table index into the inode number stored in directory
+    /// entries. Offset by one (mirroring `fuse_backend`'s ino numbering) so that `0`
+    /// is always free to mean "empty slot" in a `DirEntry`.
+    fn inode_number(index: usize) -> u32 {
+        index as u32 + 1
+    }
+
+    /// The inverse of `inode_number`.
+    fn inode_index(number: u32) -> usize {
+        (number - 1) as usize
+    }
+
+    /// On-disk format version. Bumped whenever `IDXNode`'s layout or the free-list
+    /// encoding changes, so that mounting a disk written by an older version either
+    /// migrates cleanly or fails loudly instead of silently misreading its metadata.
+    pub const FORMAT_VERSION: u32 = 5;
+    /// Version 2 disks store the free list as one byte per block instead of a packed
+    /// bitmap. `check_format_version` migrates these in place rather than rejecting them.
+    const LEGACY_BYTE_PER_BLOCK_VERSION: u32 = 2;
+    /// Size in bytes of the version stamp written right after the inode table.
+    pub const VERSION_STAMP_SIZE: usize = 4;
+    /// Number of blocks the free list, inode table, and version stamp occupy. `IDXNode`
+    /// now carries enough metadata (mode, ownership, timestamps) that this can span more
+    /// than the single block it used to.
+    const METADATA_BLOCKS: usize =
+        (IDXNODE_SIZE * MAX_INODES + VERSION_STAMP_SIZE).div_ceil(BLOCK_SIZE);
+    /// Absolute byte offset of the version stamp, immediately after the inode table.
+    pub const VERSION_OFFSET: usize = FREE_BLOCK_SIZE + IDXNODE_SIZE * MAX_INODES;
+    /// Block reserved for the write-ahead journal, right after the metadata blocks. It
+    /// is marked permanently allocated in the free block list so it's never handed out
+    /// to a file.
+    const JOURNAL_BLOCK: usize = METADATA_BLOCKS;
+    /// Total number of blocks (starting at block 0) that are never available for file
+    /// data: the metadata blocks plus the journal block.
+    pub const RESERVED_BLOCKS: usize = METADATA_BLOCKS + 1;
+    /// Magic value written at the start of a pending (not-yet-committed) journal record.
+    const JOURNAL_MAGIC_PENDING: u32 = 0x4A524E4C; // "JRNL"
+    /// Magic value for an empty/committed journal slot.
+    const JOURNAL_MAGIC_EMPTY: u32 = 0;
+    /// Most inodes a single journal record can cover. Every multi-inode operation in
+    /// this crate (`create_file`/`mkdir`/`symlink`/`unlink`) touches exactly a parent
+    /// and a child, so two is enough; `commit` asserts it's never exceeded.
+    const JOURNAL_MAX_INODES: usize = 2;
+
+    /// Maximum number of symlinks `resolve_path` will follow in a single resolution
+    /// before giving up, breaking any symlink loop instead of recursing forever.
+    const MAX_SYMLINK_HOPS: u32 = 40;
+
+    /// File metadata returned by `stat`, modeled after the attributes a POSIX/FUSE
+    /// filesystem reports (`man 2 stat`).
+    #[derive(Debug, Clone, Copy)]
+    pub struct FileAttr {
+        pub size: u64,
+        pub mode: u16,
+        pub uid: u32,
+        pub gid: u32,
+        pub atime: u64,
+        pub mtime: u64,
+        pub ctime: u64,
+        pub links: u32,
+    }
+
+    /// Filesystem-wide capacity/usage summary returned by `statfs`.
+    #[derive(Debug, Clone, Copy)]
+    pub struct FsStats {
+        pub total_blocks: usize,
+        pub free_blocks: usize,
+        pub total_inodes: usize,
+        pub free_inodes: usize,
+    }
+
+    /// A single entry returned by `readdir`: a child's name, the inode number its
+    /// `DirEntry` points at, and whether that child is itself a symlink (rather than
+    /// its target).
+    #[derive(Debug, Clone)]
+    pub struct DirEntryInfo {
+        pub name: String,
+        pub inode: u32,
+        pub is_symlink: bool,
+    }
+
+    fn now_secs() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    /// A packed one-bit-per-block free/used bitmap backing the block allocator. Wraps
+    /// the raw on-disk bytes so callers go through `allocate`/`deallocate`/`query`
+    /// instead of poking individual bits themselves.
+    #[derive(Clone, Copy)]
+    struct Bitmap {
+        bytes: [u8; FREE_LIST_BYTES],
+        /// Byte index `allocate` resumes its scan from, so repeated allocations don't
+        /// re-scan bytes already known to be full. Not part of the on-disk encoding
+        /// (see `as_bytes`) — a bitmap just decoded off disk always starts at 0.
+        /// `deallocate` pulls this back down whenever it frees a block before it,
+        /// since `allocate` would otherwise skip past that newly-free block for good.
+        scan_cursor: usize,
+    }
+
+    impl Bitmap {
+        fn zeroed() -> Bitmap {
+            Bitmap {
+                bytes: [0u8; FREE_LIST_BYTES],
+                scan_cursor: 0,
+            }
+        }
+
+        fn as_bytes(&self) -> &[u8; FREE_LIST_BYTES] {
+            &self.bytes
+        }
+
+        /// Returns whether `block` is currently marked used.
+        fn query(&self, block: usize) -> bool {
+            self.bytes[block / 8] & (1 << (block % 8)) != 0
+        }
+
+        /// Marks `block` as used, regardless of its previous state.
+        fn mark_used(&mut self, block: usize) {
+            self.bytes[block / 8] |= 1 << (block % 8);
+        }
 
-    impl MyFileSystem {
+        /// Marks `block` as free again.
+        fn deallocate(&mut self, block: usize) {
+            self.bytes[block / 8] &= !(1 << (block % 8));
+            self.scan_cursor = self.scan_cursor.min(block / 8);
+        }
+
+        /// Finds the first free block, marks it used, and returns its index. Resumes
+        /// from `scan_cursor` instead of byte 0 so repeated allocations don't rescan
+        /// bytes already known to be full, and skips a fully-allocated byte (`0xFF`)
+        /// outright instead of testing each of its bits individually.
+        fn allocate(&mut self) -> Option<u32> {
+            for byte_index in self.scan_cursor..self.bytes.len() {
+                let byte = &mut self.bytes[byte_index];
+                if *byte == 0xFF {
+                    continue;
+                }
+                for bit in 0..8 {
+                    let block = byte_index * 8 + bit;
+                    if block >= NUM_BLOCKS {
+                        break;
+                    }
+                    if *byte & (1 << bit) == 0 {
+                        *byte |= 1 << bit;
+                        self.scan_cursor = byte_index;
+                        return Some(block as u32);
+                    }
+                }
+            }
+            None
+        }
+
+        /// Number of blocks not currently marked used.
+        fn count_free(&self) -> usize {
+            (0..NUM_BLOCKS).filter(|&block| !self.query(block)).count()
+        }
+    }
+
+    /// Builds a freshly-formatted disk image: every metadata/journal block marked used
+    /// in the free bitmap, the root directory's inode and its `.`/`..` data block
+    /// written, the version stamp written, and everything else zeroed. Shared
+    /// by `new_in_memory` and the `create_fs` binary so the on-disk layout only has to be
+    /// described in one place.
+    pub fn format_image() -> Vec<u8> {
+        let mut image = vec![0u8; DISK_SIZE];
+        let mut bitmap = Bitmap::zeroed();
+        for block in 0..RESERVED_BLOCKS {
+            bitmap.mark_used(block);
+        }
+
+        // The root directory always exists at ROOT_INODE_INDEX, holding "." and
+        // ".." entries that both point back at itself.
+        let root_data_block = bitmap
+            .allocate()
+            .expect("disk too small to hold a root directory block");
+        let mut root_inode = IDXNode::decode([0u8; IDXNODE_SIZE]);
+        root_inode.size = 1;
+        root_inode.block_pointers[0] = root_data_block;
+        root_inode.used = 1;
+        root_inode.mode = DEFAULT_DIR_MODE;
+        root_inode.links = 2;
+        let created_at = now_secs();
+        root_inode.atime = created_at;
+        root_inode.mtime = created_at;
+        root_inode.ctime = created_at;
+        let root_ino = inode_number(ROOT_INODE_INDEX);
+        image[FREE_BLOCK_SIZE..FREE_BLOCK_SIZE + IDXNODE_SIZE].copy_from_slice(&root_inode.encode());
+
+        let mut root_data = [0u8; BLOCK_SIZE];
+        let (dot_name, dot_len) = encode_name(".").unwrap();
+        let (dotdot_name, dotdot_len) = encode_name("..").unwrap();
+        let dot = DirEntry {
+            inode: root_ino,
+            name_len: dot_len,
+            name: dot_name,
+        };
+        let dotdot = DirEntry {
+            inode: root_ino,
+            name_len: dotdot_len,
+            name: dotdot_name,
+        };
+        root_data[0..DIRENTRY_SIZE].copy_from_slice(&dot.encode());
+        root_data[DIRENTRY_SIZE..2 * DIRENTRY_SIZE].copy_from_slice(&dotdot.encode());
+        let data_offset = FREE_BLOCK_SIZE + BLOCK_SIZE * root_data_block as usize;
+        image[data_offset..data_offset + BLOCK_SIZE].copy_from_slice(&root_data);
+
+        image[0..FREE_LIST_BYTES].copy_from_slice(bitmap.as_bytes());
+        image[VERSION_OFFSET..VERSION_OFFSET + VERSION_STAMP_SIZE]
+            .copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+        image
+    }
+
+    impl MyFileSystem<FileDisk> {
         /// Simply the equivalent of the constructor in Rust. Work with this
         /// filesystem on an existing file, we can create an instance:
         ///
@@ -46,23 +610,59 @@ pub mod myfs {
         /// use cs377_filesystem::myfs;
         /// let mut my_file_system = myfs::MyFileSystem::new("disk0");
         /// ```
-        pub fn new(disk_name: &str) -> MyFileSystem {
-            MyFileSystem {
-                disk: match OpenOptions::new().read(true).write(true).open(&disk_name) {
+        pub fn new(disk_name: &str) -> MyFileSystem<FileDisk> {
+            let mut fs = MyFileSystem {
+                disk: match OpenOptions::new().read(true).write(true).open(disk_name) {
                     Ok(disk) => disk,
                     Err(_) => panic!("Could not open disk: {}", &disk_name),
                 },
-            }
+                inode_cache: [None; MAX_INODES],
+                free_block_cache: None,
+            };
+            fs.recover_journal();
+            fs.check_format_version();
+            fs
         }
+    }
 
-        /// Creates a file in the filesystem with name capped at 8 bytes, and size can range from 0 to 8
+    impl MyFileSystem<MemoryDisk> {
+        /// Formats a brand new disk image entirely in memory and returns a filesystem
+        /// backed by it, mirroring what `create_fs` does for a real file. This is the
+        /// backend the test suite uses so that tests don't depend on a `./create_fs`
+        /// binary or a `disk0` file being present on disk.
+        ///
+        /// ```
+        /// use cs377_filesystem::myfs;
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// ```
+        pub fn new_in_memory() -> MyFileSystem<MemoryDisk> {
+            let mut fs = MyFileSystem {
+                disk: Cursor::new(format_image()),
+                inode_cache: [None; MAX_INODES],
+                free_block_cache: None,
+            };
+            fs.recover_journal();
+            fs
+        }
+    }
+
+    impl<D: BlockDevice> MyFileSystem<D> {
+        /// Creates a file at `path`, resolving every component but the last as a
+        /// directory and adding an entry for the last component to it. `size` is a
+        /// block count: the first `DIRECT_POINTERS` blocks are addressed directly out
+        /// of the inode, the next `POINTERS_PER_INDIRECT_BLOCK` spill into a single
+        /// indirect block, and anything past that spills into a double indirect block
+        /// (an indirect block of indirect blocks) — the addressing scheme itself
+        /// supports files up to
+        /// `DIRECT_POINTERS + POINTERS_PER_INDIRECT_BLOCK + POINTERS_PER_INDIRECT_BLOCK^2`
+        /// blocks, though in practice `size` is capped by how many blocks are
+        /// actually free on this disk.
         /// This will check to see if creating the file is possible and will return an Err variant if not
         /// Usage:
         /// ```
         /// use cs377_filesystem::myfs;
         /// let mut my_file_system = myfs::MyFileSystem::new("disk0");
-        /// let filename: [u8; 8] = [102, 105, 108, 101, 49, 0, 0, 0]; //file1 as [u8; 8]
-        /// my_file_system.create_file(filename, 8);
+        /// my_file_system.create_file("/file1", 8, 0, 0);
         /// my_file_system.ls();
         /// ```
         ///
@@ -70,57 +670,244 @@ pub mod myfs {
         /// ```text
         /// file1
         /// ```
-        pub fn create_file(&mut self, filename: [u8; 8], size: u8) -> Result<(), String> {
-            if size > 8 {
-                return Err(String::from(format!(
-                    "Max blocks per file is 8, not {}",
-                    size
-                )));
+        pub fn create_file(&mut self, path: &str, size: u32, uid: u32, gid: u32) -> Result<(), String> {
+            let (parent_path, name) = split_path(path)?;
+            let (name_buf, name_len) = encode_name(name)?;
+            let mut free_block_list = self.get_free_block_list();
+            // The single/double indirect tiers can *address* far more blocks than a
+            // disk this size actually has (see NUM_BLOCKS), so the real limit on a
+            // file's size is how many blocks are free right now, not how many
+            // block_pointers could theoretically point at.
+            let available_blocks = free_block_list.count_free();
+            if size as usize > available_blocks {
+                return Err(format!(
+                    "Not enough free blocks: requested {}, {} available",
+                    size, available_blocks
+                ));
             }
 
-            let mut free_block_list = self.get_free_block_list();
-            let available_blocks =
-                free_block_list
-                    .iter()
-                    .fold(0, |acc, &x| if x == 0 { acc + 1 } else { acc });
-            if available_blocks < size {
-                return Err(String::from("Not enough free blocks"));
+            let (parent_index, mut parent_inode) = self.resolve_path(parent_path)?;
+            if !is_dir(&parent_inode) {
+                return Err(format!("{} is not a directory", parent_path));
+            }
+            if !check_permission(&parent_inode, uid, gid, Access::Write) {
+                return Err(format!("{}: permission denied", parent_path));
             }
 
             // Find an unused inode if one exists, otherwise return Err
-            let mut inode = self.get_first_inode_conditional_on(|i| i.used == 0)?;
+            let (inode_index, mut inode) = self.get_first_inode_conditional_on(|i| i.used == 0)?;
             inode.used = 1;
-            inode.name = filename;
+            inode.name = name_buf;
             inode.size = size;
+            inode.mode = DEFAULT_FILE_MODE;
+            inode.uid = uid;
+            inode.gid = gid;
+            inode.links = 1;
+            let created_at = now_secs();
+            inode.atime = created_at;
+            inode.mtime = created_at;
+            inode.ctime = created_at;
+
+            self.allocate_blocks(&mut inode, &mut free_block_list, size as usize)?;
+            self.add_dir_entry(
+                &mut parent_inode,
+                &mut free_block_list,
+                name_buf,
+                name_len,
+                inode_number(inode_index),
+            )?;
+
+            self.commit(
+                &[(parent_index, parent_inode), (inode_index, inode)],
+                free_block_list,
+            );
+            Ok(())
+        }
 
-            // Allocate available blocks for file into inode's block_pointers array.
-            let mut blocks_allocated = 0;
-            let mut i = 0;
-            while i < FREE_BLOCK_SIZE as u32 && blocks_allocated < size as usize {
-                if free_block_list[i as usize] == 0 {
-                    free_block_list[i as usize] = 1;
-                    inode.block_pointers[blocks_allocated] = i;
-                    blocks_allocated += 1;
+        /// Creates a directory at `path`, resolving every component but the last as
+        /// the parent directory and adding an entry for the last component to it. The
+        /// new directory is seeded with `.` and `..` entries, same as the root.
+        /// Usage:
+        /// ```
+        /// use cs377_filesystem::myfs;
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// my_file_system.mkdir("/subdir", 0, 0).unwrap();
+        /// my_file_system.create_file("/subdir/file1", 1, 0, 0).unwrap();
+        /// ```
+        pub fn mkdir(&mut self, path: &str, uid: u32, gid: u32) -> Result<(), String> {
+            let (parent_path, name) = split_path(path)?;
+            let (name_buf, name_len) = encode_name(name)?;
+            let mut free_block_list = self.get_free_block_list();
+            let (parent_index, mut parent_inode) = self.resolve_path(parent_path)?;
+            if !is_dir(&parent_inode) {
+                return Err(format!("{} is not a directory", parent_path));
+            }
+            if !check_permission(&parent_inode, uid, gid, Access::Write) {
+                return Err(format!("{}: permission denied", parent_path));
+            }
+
+            let (inode_index, mut inode) = self.get_first_inode_conditional_on(|i| i.used == 0)?;
+            inode.used = 1;
+            inode.name = name_buf;
+            inode.size = 1;
+            inode.mode = DEFAULT_DIR_MODE;
+            inode.uid = uid;
+            inode.gid = gid;
+            inode.links = 2;
+            let created_at = now_secs();
+            inode.atime = created_at;
+            inode.mtime = created_at;
+            inode.ctime = created_at;
+
+            self.allocate_blocks(&mut inode, &mut free_block_list, 1)?;
+            let data_block = self.resolve_block(&inode, 0);
+            let self_ino = inode_number(inode_index);
+            let parent_ino = inode_number(parent_index);
+            let (dot_name, dot_len) = encode_name(".").unwrap();
+            let (dotdot_name, dotdot_len) = encode_name("..").unwrap();
+            let mut raw = [0u8; BLOCK_SIZE];
+            raw[0..DIRENTRY_SIZE].copy_from_slice(
+                &DirEntry {
+                    inode: self_ino,
+                    name_len: dot_len,
+                    name: dot_name,
+                }
+                .encode(),
+            );
+            raw[DIRENTRY_SIZE..2 * DIRENTRY_SIZE].copy_from_slice(
+                &DirEntry {
+                    inode: parent_ino,
+                    name_len: dotdot_len,
+                    name: dotdot_name,
                 }
-                i += 1;
+                .encode(),
+            );
+            self.write_raw_block(data_block, &raw);
+
+            self.add_dir_entry(
+                &mut parent_inode,
+                &mut free_block_list,
+                name_buf,
+                name_len,
+                self_ino,
+            )?;
+            parent_inode.links += 1;
+
+            self.commit(
+                &[(parent_index, parent_inode), (inode_index, inode)],
+                free_block_list,
+            );
+            Ok(())
+        }
+
+        /// Creates a symlink at `linkname` pointing at `target`. A target short enough
+        /// to fit in `FAST_SYMLINK_MAX_LEN` bytes is packed directly into the inode's
+        /// `block_pointers` (a "fast" symlink, needing no data block); a longer one
+        /// spills into a single allocated block. `target` is stored as given and is
+        /// not itself resolved or validated — `resolve_path` follows it (and, for a
+        /// relative target, resolves it against `linkname`'s own parent directory)
+        /// the next time something traverses through `linkname`.
+        /// Usage:
+        /// ```
+        /// use cs377_filesystem::myfs;
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// my_file_system.create_file("/file1", 1, 0, 0).unwrap();
+        /// my_file_system.symlink("/file1", "/link1", 0, 0).unwrap();
+        /// assert_eq!(my_file_system.readlink("/link1").unwrap(), "/file1");
+        /// ```
+        pub fn symlink(
+            &mut self,
+            target: &str,
+            linkname: &str,
+            uid: u32,
+            gid: u32,
+        ) -> Result<(), String> {
+            if target.len() > BLOCK_SIZE {
+                return Err(format!(
+                    "symlink target too long ({} bytes, max {})",
+                    target.len(),
+                    BLOCK_SIZE
+                ));
+            }
+
+            let (parent_path, name) = split_path(linkname)?;
+            let (name_buf, name_len) = encode_name(name)?;
+            let mut free_block_list = self.get_free_block_list();
+            let (parent_index, mut parent_inode) = self.resolve_path(parent_path)?;
+            if !is_dir(&parent_inode) {
+                return Err(format!("{} is not a directory", parent_path));
             }
+            if !check_permission(&parent_inode, uid, gid, Access::Write) {
+                return Err(format!("{}: permission denied", parent_path));
+            }
+
+            let (inode_index, mut inode) = self.get_first_inode_conditional_on(|i| i.used == 0)?;
+            inode.used = 1;
+            inode.name = name_buf;
+            inode.mode = DEFAULT_SYMLINK_MODE;
+            inode.uid = uid;
+            inode.gid = gid;
+            inode.links = 1;
+            let created_at = now_secs();
+            inode.atime = created_at;
+            inode.mtime = created_at;
+            inode.ctime = created_at;
 
-            self.write_inode(inode);
-            self.write_free_block_list(free_block_list);
-            return Ok(());
+            inode.size = target.len() as u32;
+            if target.len() <= FAST_SYMLINK_MAX_LEN {
+                inode.block_pointers = encode_fast_symlink_target(target);
+            } else {
+                let block = free_block_list
+                    .allocate()
+                    .ok_or_else(|| String::from("Not enough free blocks"))?;
+                inode.block_pointers[0] = block;
+                let mut raw = [0u8; BLOCK_SIZE];
+                raw[..target.len()].copy_from_slice(target.as_bytes());
+                self.write_raw_block(block, &raw);
+            }
+
+            self.add_dir_entry(
+                &mut parent_inode,
+                &mut free_block_list,
+                name_buf,
+                name_len,
+                inode_number(inode_index),
+            )?;
+
+            self.commit(
+                &[(parent_index, parent_inode), (inode_index, inode)],
+                free_block_list,
+            );
+            Ok(())
         }
 
-        /// Deletes a file from the filesystem by marking the inode as unused and marking each block
-        /// that was allocated for the file as unused if file exists. Otherwise, it returns the Err variant.
+        /// Returns the target stored at the symlink `path`, without following it.
         /// Usage:
         /// ```
         /// use cs377_filesystem::myfs;
-        /// let mut my_file_system = myfs::MyFileSystem::new("disk0");
-        /// let filename1: [u8; 8] = [102, 105, 108, 101, 49, 0, 0, 0]; //file1 as [u8; 8]
-        /// let filename2: [u8; 8] = [102, 105, 108, 101, 50, 0, 0, 0]; //file2 as [u8; 8]
-        /// my_file_system.create_file(filename1, 8);
-        /// my_file_system.create_file(filename2, 4);
-        /// my_file_system.delete_file(filename1);
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// my_file_system.symlink("/nowhere", "/dangling", 0, 0).unwrap();
+        /// assert_eq!(my_file_system.readlink("/dangling").unwrap(), "/nowhere");
+        /// ```
+        pub fn readlink(&mut self, path: &str) -> Result<String, String> {
+            let (_, inode) = self.resolve_path_no_follow_last(path)?;
+            if !is_symlink(&inode) {
+                return Err(format!("{}: not a symbolic link", path));
+            }
+            Ok(self.read_symlink_target(&inode))
+        }
+
+        /// Removes the entry named by the last component of `path` from its parent
+        /// directory and decrements the target inode's link count, freeing its blocks
+        /// and marking it unused once that count reaches zero. Refuses to remove a
+        /// non-empty directory.
+        /// Usage:
+        /// ```
+        /// use cs377_filesystem::myfs;
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// my_file_system.create_file("/file1", 8, 0, 0).unwrap();
+        /// my_file_system.create_file("/file2", 4, 0, 0).unwrap();
+        /// my_file_system.unlink("/file1").unwrap();
         /// my_file_system.ls();
         /// ```
         ///
@@ -128,15 +915,59 @@ pub mod myfs {
         /// ```text
         /// file2
         /// ```
-        pub fn delete_file(&mut self, filename: [u8; 8]) -> Result<(), String> {
+        pub fn unlink(&mut self, path: &str) -> Result<(), String> {
+            let (parent_path, name) = split_path(path)?;
+            if name == "." || name == ".." {
+                return Err(format!("{}: cannot unlink `.` or `..`", path));
+            }
             let mut free_block_list = self.get_free_block_list();
-            let mut inode = self.get_first_inode_conditional_on(|x| x.name == filename)?;
-            for i in 0..inode.size {
-                free_block_list[inode.block_pointers[i as usize] as usize] = 0;
+            let (parent_index, mut parent_inode) = self.resolve_path(parent_path)?;
+            let (slot_block, slot_offset, child_ino) = self
+                .find_dir_entry_slot(&parent_inode, name)
+                .ok_or_else(|| format!("{}: no such file or directory", path))?;
+            let child_index = inode_index(child_ino);
+            let mut child_inode = self.get_inode(child_index);
+
+            if is_dir(&child_inode) {
+                let entry_count = self
+                    .read_dir_entries(&child_inode)
+                    .into_iter()
+                    .filter(|e| e.name_str() != "." && e.name_str() != "..")
+                    .count();
+                if entry_count > 0 {
+                    return Err(format!("{}: directory not empty", path));
+                }
+                parent_inode.links -= 1;
             }
-            inode.used = 0;
-            self.write_inode(inode);
-            self.write_free_block_list(free_block_list);
+
+            let mut raw = self.read_raw_block(slot_block);
+            raw[slot_offset..slot_offset + DIRENTRY_SIZE].copy_from_slice(&DirEntry {
+                inode: 0,
+                name_len: 0,
+                name: [0u8; MAX_NAME_LEN],
+            }.encode());
+            self.write_raw_block(slot_block, &raw);
+
+            // A directory's link count is 2 from creation (its own "." entry plus the
+            // parent's entry for it), so removing it drops both at once rather than
+            // just the parent's entry.
+            let child_link_delta = if is_dir(&child_inode) { 2 } else { 1 };
+            child_inode.links = child_inode.links.saturating_sub(child_link_delta);
+            if child_inode.links == 0 {
+                if is_symlink(&child_inode) {
+                    if child_inode.size as usize > FAST_SYMLINK_MAX_LEN {
+                        free_block_list.deallocate(child_inode.block_pointers[0] as usize);
+                    }
+                } else {
+                    self.free_blocks(&child_inode, &mut free_block_list);
+                }
+                child_inode.used = 0;
+            }
+
+            self.commit(
+                &[(parent_index, parent_inode), (child_index, child_inode)],
+                free_block_list,
+            );
             Ok(())
         }
 
@@ -151,93 +982,235 @@ pub mod myfs {
         /// Will print nothing, as there are no files and just an empty filesystem.
         /// See other doctests for examples where something is printed by ls()
         pub fn ls(&mut self) {
-            for i in 0..MAX_INODES {
-                let inode = self.get_inode(i);
-                if inode.used == 1 {
-                    println!("{}", std::str::from_utf8(&inode.name).unwrap());
+            if let Ok(entries) = self.readdir("/") {
+                for entry in entries {
+                    if entry.name != "." && entry.name != ".." {
+                        if entry.is_symlink {
+                            println!("{}@", entry.name);
+                        } else {
+                            println!("{}", entry.name);
+                        }
+                    }
                 }
             }
         }
 
-        /// Reads block block_num out of file and returns Ok(contents) if it exists
-        /// and returns Err otherwise.
+        /// Lists the entries of the directory at `path`, including `.` and `..`.
         /// Usage:
         /// ```
         /// use cs377_filesystem::myfs;
-        /// let mut my_file_system = myfs::MyFileSystem::new("disk0");
-        /// let filename: [u8; 8] = [102, 105, 108, 101, 49, 0, 0, 0]; //file1 as [u8; 8]
-        /// my_file_system.create_file(filename, 8);
-        /// println!(my_file_system.read(filename, 7));
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// my_file_system.mkdir("/subdir", 0, 0).unwrap();
+        /// let names: Vec<_> = my_file_system.readdir("/").unwrap().into_iter().map(|e| e.name).collect();
+        /// assert!(names.contains(&"subdir".to_string()));
         /// ```
-        ///
-        /// This will output one of the following assuming disk0 exists and the read was successful or not:
-        /// ```text
-        /// Ok("111....1111")
+        pub fn readdir(&mut self, path: &str) -> Result<Vec<DirEntryInfo>, String> {
+            let (_, inode) = self.resolve_path(path)?;
+            if !is_dir(&inode) {
+                return Err(format!("{} is not a directory", path));
+            }
+            let entries = self.read_dir_entries(&inode);
+            Ok(entries
+                .into_iter()
+                .map(|entry| {
+                    let child = self.get_inode(inode_index(entry.inode));
+                    DirEntryInfo {
+                        name: entry.name_str().to_string(),
+                        inode: entry.inode,
+                        is_symlink: is_symlink(&child),
+                    }
+                })
+                .collect())
+        }
+
+        /// Reads block block_num out of the file at `path` and returns Ok(contents) if
+        /// it exists and `uid`/`gid` have read permission on it, and returns Err otherwise.
+        /// Usage:
         /// ```
-        /// Or:
-        /// ```text
-        /// Err("Some error description")
+        /// use cs377_filesystem::myfs;
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// my_file_system.create_file("/file1", 8, 0, 0).unwrap();
+        /// let block = my_file_system.read("/file1", 7, 0, 0).unwrap();
+        /// assert_eq!(block.len(), myfs::BLOCK_SIZE);
         /// ```
         pub fn read(
             &mut self,
-            filename: [u8; 8],
-            block_num: u8,
+            path: &str,
+            block_num: u32,
+            uid: u32,
+            gid: u32,
         ) -> Result<[u8; BLOCK_SIZE], String> {
-            let inode = self.get_first_inode_conditional_on(|x| x.name == filename)?;
+            let (inode_index, mut inode) = self.resolve_path(path)?;
+            if !check_permission(&inode, uid, gid, Access::Read) {
+                return Err(format!("{}: permission denied", path));
+            }
             if inode.size <= block_num {
                 return Err(format!(
                     "block_num: {} exceeds capacity of inode.size: {}",
                     block_num, inode.size
                 ));
             }
-            let block = inode.block_pointers[block_num as usize];
-            let mut buf: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
-            self.disk
-                .seek(std::io::SeekFrom::Start(
-                    (FREE_BLOCK_SIZE + BLOCK_SIZE * block as usize) as u64,
-                ))
-                .unwrap();
-            self.disk.read(&mut buf).unwrap();
-            return Ok(buf);
+            let block = self.resolve_block(&inode, block_num);
+            let buf = self.read_raw_block(block);
+            inode.atime = now_secs();
+            self.write_inode_direct(inode_index, inode);
+            Ok(buf)
         }
 
-        /// Writes to block block_num of file and returns Ok(()) if successful and return Err otherwise.
+        /// Writes to block block_num of the file at `path` and returns Ok(()) if
+        /// `uid`/`gid` have write permission on it and the write succeeds, and returns
+        /// Err otherwise.
         /// Usage:
         /// ```
         /// use cs377_filesystem::myfs;
-        /// let mut my_file_system = myfs::MyFileSystem::new("disk0");
-        /// let filename: [u8; 8] = [102, 105, 108, 101, 49, 0, 0, 0]; //file1 as [u8; 8]
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
         /// let my_new_data = [69u8; myfs::BLOCK_SIZE];
-        /// my_file_system.create_file(filename, 3);
-        /// my_filesystem.write(filename, &my_new_data, 2)
-        /// println!(my_file_system.read(filename, 2));
-        /// ```
-        ///
-        /// This will output one of the following assuming disk0 exists and the write was successful or not:
-        /// ```text
-        /// Ok("EEE....EEE")
-        /// ```
-        /// Or
-        /// ```text
-        /// Err("Some error description")
+        /// my_file_system.create_file("/file1", 3, 0, 0).unwrap();
+        /// my_file_system.write("/file1", 2, &my_new_data, 0, 0).unwrap();
+        /// assert_eq!(my_file_system.read("/file1", 2, 0, 0).unwrap(), my_new_data);
         /// ```
         pub fn write(
             &mut self,
-            filename: [u8; 8],
-            block_num: u8,
+            path: &str,
+            block_num: u32,
             write_buf: &[u8; BLOCK_SIZE],
+            uid: u32,
+            gid: u32,
         ) -> Result<(), String> {
-            let inode = self.get_first_inode_conditional_on(|x| x.name == filename)?;
-            let block = inode.block_pointers[block_num as usize];
-            self.disk
-                .seek(std::io::SeekFrom::Start(
-                    (FREE_BLOCK_SIZE + BLOCK_SIZE * block as usize) as u64,
-                ))
-                .unwrap();
-            self.disk.write(write_buf).unwrap();
+            let (inode_index, mut inode) = self.resolve_path(path)?;
+            if !check_permission(&inode, uid, gid, Access::Write) {
+                return Err(format!("{}: permission denied", path));
+            }
+            if inode.size <= block_num {
+                return Err(format!(
+                    "block_num: {} exceeds capacity of inode.size: {}",
+                    block_num, inode.size
+                ));
+            }
+            let block = self.resolve_block(&inode, block_num);
+            self.write_raw_block(block, write_buf);
+            inode.mtime = now_secs();
+            self.write_inode_direct(inode_index, inode);
+            Ok(())
+        }
+
+        /// Changes the permission bits of `path` to `mode` (the low 12 bits: setuid/
+        /// setgid/sticky plus owner/group/other rwx), leaving its type bits untouched.
+        ///
+        /// ```
+        /// use cs377_filesystem::myfs;
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// my_file_system.create_file("/file1", 1, 0, 0).unwrap();
+        /// my_file_system.chmod("/file1", 0o600).unwrap();
+        /// assert_eq!(my_file_system.stat("/file1").unwrap().mode & 0o7777, 0o600);
+        /// ```
+        pub fn chmod(&mut self, path: &str, mode: u16) -> Result<(), String> {
+            let free_block_list = self.get_free_block_list();
+            let (inode_index, mut inode) = self.resolve_path(path)?;
+            inode.mode = (inode.mode & !0o7777) | (mode & 0o7777);
+            inode.ctime = now_secs();
+            self.commit(&[(inode_index, inode)], free_block_list);
+            Ok(())
+        }
+
+        /// Changes the owning `uid`/`gid` of `path`.
+        ///
+        /// ```
+        /// use cs377_filesystem::myfs;
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// my_file_system.create_file("/file1", 1, 0, 0).unwrap();
+        /// my_file_system.chown("/file1", 501, 20).unwrap();
+        /// let attr = my_file_system.stat("/file1").unwrap();
+        /// assert_eq!((attr.uid, attr.gid), (501, 20));
+        /// ```
+        pub fn chown(&mut self, path: &str, uid: u32, gid: u32) -> Result<(), String> {
+            let free_block_list = self.get_free_block_list();
+            let (inode_index, mut inode) = self.resolve_path(path)?;
+            inode.uid = uid;
+            inode.gid = gid;
+            inode.ctime = now_secs();
+            self.commit(&[(inode_index, inode)], free_block_list);
             Ok(())
         }
 
+        /// Returns the metadata (size, mode, ownership, timestamps) for `path`,
+        /// mirroring what a POSIX `stat(2)` call would report.
+        ///
+        /// ```
+        /// use cs377_filesystem::myfs;
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// my_file_system.create_file("/file1", 2, 0, 0).unwrap();
+        /// let attr = my_file_system.stat("/file1").unwrap();
+        /// assert_eq!(attr.size, 2 * myfs::BLOCK_SIZE as u64);
+        /// ```
+        pub fn stat(&mut self, path: &str) -> Result<FileAttr, String> {
+            let (_, inode) = self.resolve_path(path)?;
+            Ok(FileAttr {
+                size: inode.size as u64 * BLOCK_SIZE as u64,
+                mode: inode.mode,
+                uid: inode.uid,
+                gid: inode.gid,
+                atime: inode.atime,
+                mtime: inode.mtime,
+                ctime: inode.ctime,
+                links: inode.links.into(),
+            })
+        }
+
+        /// Returns a filesystem-wide capacity/usage summary.
+        ///
+        /// ```
+        /// use cs377_filesystem::myfs;
+        /// let mut my_file_system = myfs::MyFileSystem::new_in_memory();
+        /// let stats = my_file_system.statfs();
+        /// // The root directory occupies inode 0 from formatting onward.
+        /// assert_eq!(stats.free_inodes, stats.total_inodes - 1);
+        /// ```
+        pub fn statfs(&mut self) -> FsStats {
+            let free_block_list = self.get_free_block_list();
+            let free_blocks = free_block_list.count_free();
+            let free_inodes = (0..MAX_INODES)
+                .filter(|&i| self.get_inode(i).used == 0)
+                .count();
+            FsStats {
+                total_blocks: NUM_BLOCKS,
+                free_blocks,
+                total_inodes: MAX_INODES,
+                free_inodes,
+            }
+        }
+
+        /// Writes every dirty cached inode and the dirty cached free-block bitmap (if
+        /// any) back out to `disk` in one pass, then clears their dirty flags. Call this
+        /// to make sure writes made through the cache (e.g. `read`/`write`'s atime/mtime
+        /// bookkeeping) are actually durable, without waiting on `close_disk`.
+        pub fn sync(&mut self) {
+            for inode_index in 0..MAX_INODES {
+                let Some(cached) = self.inode_cache[inode_index].filter(|c| c.dirty) else {
+                    continue;
+                };
+                self.disk
+                    .seek(std::io::SeekFrom::Start(
+                        (FREE_BLOCK_SIZE + IDXNODE_SIZE * inode_index) as u64,
+                    ))
+                    .unwrap();
+                self.disk.write_all(&cached.inode.encode()).unwrap();
+                self.inode_cache[inode_index] = Some(CachedInode {
+                    dirty: false,
+                    ..cached
+                });
+            }
+            if let Some(cached) = self.free_block_cache.filter(|c| c.dirty) {
+                self.disk.seek(std::io::SeekFrom::Start(0)).unwrap();
+                self.disk.write_all(cached.bitmap.as_bytes()).unwrap();
+                self.free_block_cache = Some(CachedBitmap {
+                    dirty: false,
+                    ..cached
+                });
+            }
+            self.disk.flush().unwrap();
+        }
+
         // Closes the disk after usage. This is mainly to coincide with the implementation on the C++ side
         // Rusts safety guarentees makes sure that after this function is called on an instance of MyFileSystem,
         // it cannot be referenced again as it takes ownership of self (and then subsequently dropping the owned
@@ -279,35 +1252,46 @@ pub mod myfs {
         ///     |                           ^^^^
         /// error: aborting due to previous error
         /// ```
-        pub fn close_disk(self) {
+        pub fn close_disk(mut self) {
+            self.sync();
             drop(self.disk);
         }
     }
 
     // This impl block defines private/helper functions for internal implementation
-    impl MyFileSystem {
-        /// Gets an inode at index in inode table
+    impl<D: BlockDevice> MyFileSystem<D> {
+        /// Gets an inode at index in inode table, consulting `inode_cache` first so that
+        /// repeated lookups (e.g. `statfs` scanning all of `MAX_INODES`) only fault in from
+        /// disk once per inode.
         fn get_inode(&mut self, inode_index: usize) -> IDXNode {
+            if let Some(cached) = self.inode_cache[inode_index] {
+                return cached.inode;
+            }
             let mut inode_buffer = [0u8; IDXNODE_SIZE];
             self.disk
                 .seek(std::io::SeekFrom::Start(
                     (FREE_BLOCK_SIZE + IDXNODE_SIZE * inode_index) as u64,
                 ))
                 .unwrap();
-            self.disk.read(&mut inode_buffer).unwrap();
-            unsafe { std::mem::transmute::<[u8; IDXNODE_SIZE], IDXNode>(inode_buffer) }
+            self.disk.read_exact(&mut inode_buffer).unwrap();
+            let inode = IDXNode::decode(inode_buffer);
+            self.inode_cache[inode_index] = Some(CachedInode {
+                inode,
+                dirty: false,
+            });
+            inode
         }
 
-        /// Gets and returns inode conditional on a filter function f
+        /// Gets and returns the (index, inode) of the first inode for which f returns true.
         /// If f returns true for an instance, then the inode is returned. Otherwise an Err
         fn get_first_inode_conditional_on(
             &mut self,
             f: impl Fn(&IDXNode) -> bool,
-        ) -> Result<IDXNode, &str> {
+        ) -> Result<(usize, IDXNode), &str> {
             for i in 0..MAX_INODES {
                 let inode = self.get_inode(i);
                 if f(&inode) {
-                    return Ok(inode);
+                    return Ok((i, inode));
                 }
             }
             Err("Could not find inode meeting condition")
@@ -315,77 +1299,929 @@ pub mod myfs {
 
         /// This function writes the free_block_list back out to the disk.
         /// This is done after in memory changes to the free_block_list for example when allocating
-        /// blocks for a new file.
-        fn write_free_block_list(&mut self, free_block_list: [u8; FREE_BLOCK_SIZE]) {
+        /// blocks for a new file. Writes through immediately (unlike `write_inode_direct`)
+        /// because it's only ever called as part of applying a journaled commit, which
+        /// needs the real write to have happened before the journal record is cleared.
+        fn write_free_block_list(&mut self, free_block_list: Bitmap) {
             self.disk.seek(std::io::SeekFrom::Start(0)).unwrap();
-            self.disk.write(&free_block_list).unwrap();
+            self.disk.write_all(free_block_list.as_bytes()).unwrap();
+            self.free_block_cache = Some(CachedBitmap {
+                bitmap: free_block_list,
+                dirty: false,
+            });
         }
 
-        /// This function writes the inode in place over the inode immediately before the current cursor position
-        /// It makes the assumption that the cursor position is already placed at the end of the inode position
-        /// that overwriting is desired and it does not take in an index as a parameter.
-        fn write_inode(&mut self, inode: IDXNode) {
-            let inode_buffer = unsafe { std::mem::transmute::<IDXNode, [u8; IDXNODE_SIZE]>(inode) };
+        /// Commits one or more inode updates and the free-block-list update they depend
+        /// on as a single transaction: the intended bytes are journaled and synced
+        /// first, then applied to their real locations, then the journal entry is
+        /// marked committed. `create_file`/`mkdir`/`symlink`/`unlink` all touch a
+        /// parent inode and a child inode together, so both go in the same record —
+        /// journaling them as two independent transactions would let a crash between
+        /// them commit one write without the other. If the process dies between the
+        /// real writes, `recover_journal` replays the journal on the next
+        /// `MyFileSystem::new`/`new_in_memory` instead of leaving the disk half-written.
+        fn commit(&mut self, updates: &[(usize, IDXNode)], free_block_list: Bitmap) {
+            assert!(
+                updates.len() <= JOURNAL_MAX_INODES,
+                "a single transaction can only journal up to {} inodes, got {}",
+                JOURNAL_MAX_INODES,
+                updates.len()
+            );
+            let encoded: Vec<(usize, [u8; IDXNODE_SIZE])> = updates
+                .iter()
+                .map(|&(index, inode)| (index, inode.encode()))
+                .collect();
+            self.write_journal_record(&encoded, &free_block_list);
+            self.apply_journal_record(&encoded, &free_block_list);
+            self.clear_journal_record();
+        }
+
+        /// Applies an already-journaled set of (inode_index, inode_bytes) updates and
+        /// the free-block-list update that goes with them to their real on-disk
+        /// locations.
+        fn apply_journal_record(
+            &mut self,
+            updates: &[(usize, [u8; IDXNODE_SIZE])],
+            free_block_list: &Bitmap,
+        ) {
+            for &(inode_index, inode_bytes) in updates {
+                self.disk
+                    .seek(std::io::SeekFrom::Start(
+                        (FREE_BLOCK_SIZE + IDXNODE_SIZE * inode_index) as u64,
+                    ))
+                    .unwrap();
+                self.disk.write_all(&inode_bytes).unwrap();
+                self.inode_cache[inode_index] = Some(CachedInode {
+                    inode: IDXNode::decode(inode_bytes),
+                    dirty: false,
+                });
+            }
+            self.write_free_block_list(*free_block_list);
+        }
+
+        /// Writes `inode` in place at `inode_index`, independent of journaling. Used for
+        /// metadata-only touch-ups (atime/mtime bookkeeping on `read`/`write`) that don't
+        /// need crash-consistency with the free block list. Only updates `inode_cache`
+        /// and marks the entry dirty; `sync`/`close_disk` writes it out for real.
+        fn write_inode_direct(&mut self, inode_index: usize, inode: IDXNode) {
+            self.inode_cache[inode_index] = Some(CachedInode { inode, dirty: true });
+        }
+
+        /// Reads the version stamp and migrates or panics as appropriate. A version-2
+        /// disk is migrated in place (see `migrate_byte_per_block_free_list`); anything
+        /// else that doesn't match `FORMAT_VERSION` panics rather than silently
+        /// misreading an inode table or free list laid out for a different version.
+        fn check_format_version(&mut self) {
             self.disk
-                .seek(std::io::SeekFrom::Current(-(IDXNODE_SIZE as i64)))
+                .seek(std::io::SeekFrom::Start(VERSION_OFFSET as u64))
                 .unwrap();
-            self.disk.write(&inode_buffer).unwrap();
+            let mut version_buf = [0u8; VERSION_STAMP_SIZE];
+            self.disk.read_exact(&mut version_buf).unwrap();
+            let version = u32::from_le_bytes(version_buf);
+            if version == LEGACY_BYTE_PER_BLOCK_VERSION {
+                self.migrate_byte_per_block_free_list();
+                return;
+            }
+            if version != FORMAT_VERSION {
+                panic!(
+                    "Unsupported disk format version {} (expected {})",
+                    version, FORMAT_VERSION
+                );
+            }
         }
 
-        /// This returns the free_block_list in byte array format for easy traversal and availability checking.
-        fn get_free_block_list(&mut self) -> [u8; FREE_BLOCK_SIZE] {
+        /// Converts a version-2 disk's byte-per-block free list (one byte per block,
+        /// `0`/`1`) into the packed bitmap encoding, then stamps the disk with the
+        /// current `FORMAT_VERSION`. The inode table and data blocks are untouched; only the
+        /// free-list region's encoding changes.
+        fn migrate_byte_per_block_free_list(&mut self) {
             self.disk.seek(std::io::SeekFrom::Start(0)).unwrap();
-            let mut free_block_list: [u8; FREE_BLOCK_SIZE] = [0; FREE_BLOCK_SIZE];
-            self.disk.read(&mut free_block_list).unwrap();
-            return free_block_list;
+            let mut legacy = [0u8; NUM_BLOCKS];
+            self.disk.read_exact(&mut legacy).unwrap();
+
+            let mut bitmap = Bitmap::zeroed();
+            for (block, &used) in legacy.iter().enumerate() {
+                if used != 0 {
+                    bitmap.mark_used(block);
+                }
+            }
+
+            self.disk.seek(std::io::SeekFrom::Start(0)).unwrap();
+            self.disk.write_all(bitmap.as_bytes()).unwrap();
+            self.disk
+                .write_all(&[0u8; FREE_BLOCK_SIZE - FREE_LIST_BYTES])
+                .unwrap();
+
+            self.disk
+                .seek(std::io::SeekFrom::Start(VERSION_OFFSET as u64))
+                .unwrap();
+            self.disk.write_all(&FORMAT_VERSION.to_le_bytes()).unwrap();
+            self.disk.flush().unwrap();
+        }
+
+        /// Byte offset of the journal region: a single reserved block right after the
+        /// metadata blocks, permanently marked used in the free block list.
+        fn journal_offset() -> u64 {
+            (FREE_BLOCK_SIZE + BLOCK_SIZE * JOURNAL_BLOCK) as u64
+        }
+
+        /// A cheap (non-cryptographic) checksum over a journal record's payload, just
+        /// enough to detect a torn write from a crash mid-record.
+        fn journal_checksum(
+            updates: &[(usize, [u8; IDXNODE_SIZE])],
+            free_block_list: &Bitmap,
+        ) -> u32 {
+            let mut checksum = updates.len() as u32;
+            for &(inode_index, inode_bytes) in updates {
+                checksum = checksum.wrapping_add(inode_index as u32);
+                for &byte in &inode_bytes {
+                    checksum = checksum.wrapping_add(byte as u32);
+                }
+            }
+            for &byte in free_block_list.as_bytes() {
+                checksum = checksum.wrapping_add(byte as u32);
+            }
+            checksum
+        }
+
+        /// Serializes and syncs a pending journal record before any real data is
+        /// touched. The record leads with how many inode updates it carries so
+        /// `read_journal_record` knows how many (index, inode_bytes) pairs follow.
+        fn write_journal_record(
+            &mut self,
+            updates: &[(usize, [u8; IDXNODE_SIZE])],
+            free_block_list: &Bitmap,
+        ) {
+            let checksum = Self::journal_checksum(updates, free_block_list);
+            self.disk
+                .seek(std::io::SeekFrom::Start(Self::journal_offset()))
+                .unwrap();
+            self.disk.write_all(&JOURNAL_MAGIC_PENDING.to_le_bytes()).unwrap();
+            self.disk.write_all(&(updates.len() as u32).to_le_bytes()).unwrap();
+            for &(inode_index, inode_bytes) in updates {
+                self.disk.write_all(&(inode_index as u32).to_le_bytes()).unwrap();
+                self.disk.write_all(&inode_bytes).unwrap();
+            }
+            self.disk.write_all(free_block_list.as_bytes()).unwrap();
+            self.disk.write_all(&checksum.to_le_bytes()).unwrap();
+            self.disk.flush().unwrap();
+        }
+
+        /// Zeroes the journal record's magic, marking it as committed/empty.
+        fn clear_journal_record(&mut self) {
+            self.disk
+                .seek(std::io::SeekFrom::Start(Self::journal_offset()))
+                .unwrap();
+            self.disk.write_all(&JOURNAL_MAGIC_EMPTY.to_le_bytes()).unwrap();
+            self.disk.flush().unwrap();
+        }
+
+        /// Reads the journal record, returning its payload only if its magic says it's
+        /// pending, its inode count is sane, and its checksum matches (i.e. it wasn't
+        /// torn by a mid-write crash).
+        fn read_journal_record(&mut self) -> Option<(Vec<(usize, [u8; IDXNODE_SIZE])>, Bitmap)> {
+            self.disk
+                .seek(std::io::SeekFrom::Start(Self::journal_offset()))
+                .unwrap();
+            let mut magic_buf = [0u8; 4];
+            self.disk.read_exact(&mut magic_buf).unwrap();
+            if u32::from_le_bytes(magic_buf) != JOURNAL_MAGIC_PENDING {
+                return None;
+            }
+            let mut count_buf = [0u8; 4];
+            self.disk.read_exact(&mut count_buf).unwrap();
+            let count = u32::from_le_bytes(count_buf) as usize;
+            if count > JOURNAL_MAX_INODES {
+                return None;
+            }
+            let mut updates = Vec::with_capacity(count);
+            for _ in 0..count {
+                let mut index_buf = [0u8; 4];
+                self.disk.read_exact(&mut index_buf).unwrap();
+                let inode_index = u32::from_le_bytes(index_buf) as usize;
+                let mut inode_bytes = [0u8; IDXNODE_SIZE];
+                self.disk.read_exact(&mut inode_bytes).unwrap();
+                updates.push((inode_index, inode_bytes));
+            }
+            let mut free_block_list_bytes = [0u8; FREE_LIST_BYTES];
+            self.disk.read_exact(&mut free_block_list_bytes).unwrap();
+            let free_block_list = Bitmap {
+                bytes: free_block_list_bytes,
+                scan_cursor: 0,
+            };
+            let mut checksum_buf = [0u8; 4];
+            self.disk.read_exact(&mut checksum_buf).unwrap();
+            let checksum = u32::from_le_bytes(checksum_buf);
+
+            if checksum != Self::journal_checksum(&updates, &free_block_list) {
+                return None;
+            }
+            Some((updates, free_block_list))
+        }
+
+        /// Replays a pending, valid journal record left behind by a crash mid-operation.
+        /// Called once when a filesystem is mounted.
+        fn recover_journal(&mut self) {
+            if let Some((updates, free_block_list)) = self.read_journal_record() {
+                self.apply_journal_record(&updates, &free_block_list);
+                self.clear_journal_record();
+            }
+        }
+
+        /// Returns the free-block bitmap for traversal, availability checking, and
+        /// allocation, consulting `free_block_cache` first.
+        fn get_free_block_list(&mut self) -> Bitmap {
+            if let Some(cached) = self.free_block_cache {
+                return cached.bitmap;
+            }
+            self.disk.seek(std::io::SeekFrom::Start(0)).unwrap();
+            let mut free_block_list = [0u8; FREE_LIST_BYTES];
+            self.disk.read_exact(&mut free_block_list).unwrap();
+            let bitmap = Bitmap {
+                bytes: free_block_list,
+                scan_cursor: 0,
+            };
+            self.free_block_cache = Some(CachedBitmap {
+                bitmap,
+                dirty: false,
+            });
+            bitmap
+        }
+
+        /// Reads one raw data block off disk, independent of any inode.
+        fn read_raw_block(&mut self, block: u32) -> [u8; BLOCK_SIZE] {
+            let mut buf = [0u8; BLOCK_SIZE];
+            self.disk
+                .seek(std::io::SeekFrom::Start(
+                    (FREE_BLOCK_SIZE + BLOCK_SIZE * block as usize) as u64,
+                ))
+                .unwrap();
+            self.disk.read_exact(&mut buf).unwrap();
+            buf
+        }
+
+        /// Writes one raw data block to disk, independent of any inode.
+        fn write_raw_block(&mut self, block: u32, buf: &[u8; BLOCK_SIZE]) {
+            self.disk
+                .seek(std::io::SeekFrom::Start(
+                    (FREE_BLOCK_SIZE + BLOCK_SIZE * block as usize) as u64,
+                ))
+                .unwrap();
+            self.disk.write_all(buf).unwrap();
+        }
+
+        /// Resolves `path` component by component, starting from the root directory,
+        /// via `find_dir_entry_slot`, following any symlink encountered along the way
+        /// (including as the final component). `path` with no components (`""` or
+        /// `"/"`) resolves to root.
+        fn resolve_path(&mut self, path: &str) -> Result<(usize, IDXNode), String> {
+            let mut hops = 0;
+            self.resolve_path_with_hops(path, &mut hops)
+        }
+
+        /// Like `resolve_path`, but does not follow a symlink at the very last
+        /// component — intermediate components are still followed normally. Used by
+        /// `readlink`, which needs the symlink's own inode rather than its target's.
+        fn resolve_path_no_follow_last(&mut self, path: &str) -> Result<(usize, IDXNode), String> {
+            let (parent_path, name) = split_path(path)?;
+            let (_, parent_inode) = self.resolve_path(parent_path)?;
+            if !is_dir(&parent_inode) {
+                return Err(format!("{} is not a directory", parent_path));
+            }
+            let (_, _, child_ino) = self
+                .find_dir_entry_slot(&parent_inode, name)
+                .ok_or_else(|| format!("{}: no such file or directory", path))?;
+            let index = inode_index(child_ino);
+            Ok((index, self.get_inode(index)))
+        }
+
+        /// The shared implementation behind `resolve_path`: `hops` is threaded through
+        /// every recursive call (one per symlink followed) so the total number of
+        /// hops across the whole resolution is bounded by `MAX_SYMLINK_HOPS`, not just
+        /// the hops within one call.
+        fn resolve_path_with_hops(
+            &mut self,
+            path: &str,
+            hops: &mut u32,
+        ) -> Result<(usize, IDXNode), String> {
+            let mut index = ROOT_INODE_INDEX;
+            let mut inode = self.get_inode(index);
+            let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+            for (i, component) in components.iter().enumerate() {
+                if !is_dir(&inode) {
+                    return Err(format!("{}: not a directory", component));
+                }
+                let (_, _, child_ino) = self
+                    .find_dir_entry_slot(&inode, component)
+                    .ok_or_else(|| format!("{}: no such file or directory", path))?;
+                index = inode_index(child_ino);
+                inode = self.get_inode(index);
+
+                if is_symlink(&inode) {
+                    *hops += 1;
+                    if *hops > MAX_SYMLINK_HOPS {
+                        return Err(format!("{}: too many levels of symbolic links", path));
+                    }
+                    let target = self.read_symlink_target(&inode);
+                    let resolved = if target.starts_with('/') {
+                        target
+                    } else {
+                        format!("/{}/{}", components[..i].join("/"), target)
+                    };
+                    let rest = components[i + 1..].join("/");
+                    let full_path = if rest.is_empty() {
+                        resolved
+                    } else {
+                        format!("{}/{}", resolved, rest)
+                    };
+                    return self.resolve_path_with_hops(&full_path, hops);
+                }
+            }
+            Ok((index, inode))
+        }
+
+        /// Reads the target path stored in a symlink inode, from its inline
+        /// `block_pointers` bytes (a fast symlink) or its single spilled data block.
+        fn read_symlink_target(&mut self, inode: &IDXNode) -> String {
+            let len = inode.size as usize;
+            if len <= FAST_SYMLINK_MAX_LEN {
+                decode_fast_symlink_target(&inode.block_pointers, len)
+            } else {
+                let raw = self.read_raw_block(inode.block_pointers[0]);
+                String::from_utf8_lossy(&raw[..len]).into_owned()
+            }
+        }
+
+        /// Scans `dir`'s data blocks for an entry named `name`, returning the data
+        /// block it lives in, its byte offset within that block, and the inode number
+        /// it points at. Used by both path resolution and `unlink`, which additionally
+        /// needs the slot location to clear it.
+        fn find_dir_entry_slot(&mut self, dir: &IDXNode, name: &str) -> Option<(u32, usize, u32)> {
+            for block_num in 0..dir.size {
+                let block = self.resolve_block(dir, block_num);
+                let raw = self.read_raw_block(block);
+                for slot in 0..DIRENTRIES_PER_BLOCK {
+                    let offset = slot * DIRENTRY_SIZE;
+                    let entry = DirEntry::decode(&raw[offset..offset + DIRENTRY_SIZE]);
+                    if entry.inode != 0 && entry.name_str() == name {
+                        return Some((block, offset, entry.inode));
+                    }
+                }
+            }
+            None
+        }
+
+        /// Returns every populated `DirEntry` in `dir`'s data blocks (including `.`
+        /// and `..`).
+        fn read_dir_entries(&mut self, dir: &IDXNode) -> Vec<DirEntry> {
+            let mut entries = Vec::new();
+            for block_num in 0..dir.size {
+                let block = self.resolve_block(dir, block_num);
+                let raw = self.read_raw_block(block);
+                for slot in 0..DIRENTRIES_PER_BLOCK {
+                    let offset = slot * DIRENTRY_SIZE;
+                    let entry = DirEntry::decode(&raw[offset..offset + DIRENTRY_SIZE]);
+                    if entry.inode != 0 {
+                        entries.push(entry);
+                    }
+                }
+            }
+            entries
+        }
+
+        /// Links `child_ino` into `dir` under `name`, reusing the first empty slot in
+        /// an existing data block if one exists, or growing `dir` by one block (via
+        /// `append_block`) otherwise.
+        fn add_dir_entry(
+            &mut self,
+            dir: &mut IDXNode,
+            free_block_list: &mut Bitmap,
+            name: [u8; MAX_NAME_LEN],
+            name_len: u8,
+            child_ino: u32,
+        ) -> Result<(), String> {
+            let entry = DirEntry {
+                inode: child_ino,
+                name_len,
+                name,
+            };
+            for block_num in 0..dir.size {
+                let block = self.resolve_block(dir, block_num);
+                let mut raw = self.read_raw_block(block);
+                for slot in 0..DIRENTRIES_PER_BLOCK {
+                    let offset = slot * DIRENTRY_SIZE;
+                    if DirEntry::decode(&raw[offset..offset + DIRENTRY_SIZE]).inode == 0 {
+                        raw[offset..offset + DIRENTRY_SIZE].copy_from_slice(&entry.encode());
+                        self.write_raw_block(block, &raw);
+                        return Ok(());
+                    }
+                }
+            }
+            let block = self.append_block(dir, free_block_list)?;
+            let mut raw = [0u8; BLOCK_SIZE];
+            raw[0..DIRENTRY_SIZE].copy_from_slice(&entry.encode());
+            self.write_raw_block(block, &raw);
+            Ok(())
+        }
+
+        /// Grows `inode` by exactly one block, allocating whatever indirect/double
+        /// indirect blocks are newly needed to address it, and returns the new
+        /// block's physical index. Mirrors `allocate_blocks`'s tiering, but
+        /// incrementally: directories grow one `DirEntry`-block at a time as entries
+        /// are added, rather than all at once like `create_file` does for a file's
+        /// declared size.
+        fn append_block(&mut self, inode: &mut IDXNode, free_block_list: &mut Bitmap) -> Result<u32, String> {
+            let new_block_num = inode.size as usize;
+            let new_block = free_block_list
+                .allocate()
+                .ok_or_else(|| String::from("Not enough free blocks"))?;
+
+            if new_block_num < DIRECT_POINTERS {
+                inode.block_pointers[new_block_num] = new_block;
+            } else if new_block_num - DIRECT_POINTERS < POINTERS_PER_INDIRECT_BLOCK {
+                let offset = new_block_num - DIRECT_POINTERS;
+                let indirect_block = if offset == 0 {
+                    let block = free_block_list
+                        .allocate()
+                        .ok_or_else(|| String::from("Not enough free blocks"))?;
+                    inode.block_pointers[INDIRECT_POINTER_INDEX] = block;
+                    block
+                } else {
+                    inode.block_pointers[INDIRECT_POINTER_INDEX]
+                };
+                let mut entries = self.read_indirect_block(indirect_block);
+                entries[offset] = new_block;
+                self.write_indirect_block(indirect_block, &entries);
+            } else {
+                let offset = new_block_num - DIRECT_POINTERS - POINTERS_PER_INDIRECT_BLOCK;
+                let outer_index = offset / POINTERS_PER_INDIRECT_BLOCK;
+                let inner_index = offset % POINTERS_PER_INDIRECT_BLOCK;
+                let double_indirect_block = if offset == 0 {
+                    let block = free_block_list
+                        .allocate()
+                        .ok_or_else(|| String::from("Not enough free blocks"))?;
+                    inode.block_pointers[DOUBLE_INDIRECT_POINTER_INDEX] = block;
+                    block
+                } else {
+                    inode.block_pointers[DOUBLE_INDIRECT_POINTER_INDEX]
+                };
+                let mut outer_entries = self.read_indirect_block(double_indirect_block);
+                let inner_block = if inner_index == 0 {
+                    let block = free_block_list
+                        .allocate()
+                        .ok_or_else(|| String::from("Not enough free blocks"))?;
+                    outer_entries[outer_index] = block;
+                    self.write_indirect_block(double_indirect_block, &outer_entries);
+                    block
+                } else {
+                    outer_entries[outer_index]
+                };
+                let mut inner_entries = self.read_indirect_block(inner_block);
+                inner_entries[inner_index] = new_block;
+                self.write_indirect_block(inner_block, &inner_entries);
+            }
+
+            inode.size += 1;
+            Ok(new_block)
+        }
+
+        /// Maps a logical block number within a file to the physical block index on disk,
+        /// following the indirect pointer in `block_pointers[INDIRECT_POINTER_INDEX]` once
+        /// `block_num` runs past the direct pointers, and the double indirect pointer in
+        /// `block_pointers[DOUBLE_INDIRECT_POINTER_INDEX]` once it runs past that too.
+        fn resolve_block(&mut self, inode: &IDXNode, block_num: u32) -> u32 {
+            let block_num = block_num as usize;
+            if block_num < DIRECT_POINTERS {
+                return inode.block_pointers[block_num];
+            }
+            let block_num = block_num - DIRECT_POINTERS;
+            if block_num < POINTERS_PER_INDIRECT_BLOCK {
+                let indirect_block = inode.block_pointers[INDIRECT_POINTER_INDEX];
+                let indirect_entries = self.read_indirect_block(indirect_block);
+                return indirect_entries[block_num];
+            }
+            let block_num = block_num - POINTERS_PER_INDIRECT_BLOCK;
+            let outer_block = inode.block_pointers[DOUBLE_INDIRECT_POINTER_INDEX];
+            let outer_entries = self.read_indirect_block(outer_block);
+            let inner_block = outer_entries[block_num / POINTERS_PER_INDIRECT_BLOCK];
+            let inner_entries = self.read_indirect_block(inner_block);
+            inner_entries[block_num % POINTERS_PER_INDIRECT_BLOCK]
+        }
+
+        /// Allocates `size` blocks for `inode`, filling direct pointers first, then the
+        /// single indirect block, then the double indirect block, in that order. Returns
+        /// `Err` the moment the free list runs dry, leaving `inode`/`free_block_list`
+        /// partially updated; callers only commit them to disk on success.
+        fn allocate_blocks(
+            &mut self,
+            inode: &mut IDXNode,
+            free_block_list: &mut Bitmap,
+            size: usize,
+        ) -> Result<(), String> {
+            let direct_count = size.min(DIRECT_POINTERS);
+            for slot in inode.block_pointers.iter_mut().take(direct_count) {
+                *slot = free_block_list
+                    .allocate()
+                    .ok_or_else(|| String::from("Not enough free blocks"))?;
+            }
+            if size <= DIRECT_POINTERS {
+                return Ok(());
+            }
+
+            let single_indirect_count = (size - DIRECT_POINTERS).min(POINTERS_PER_INDIRECT_BLOCK);
+            let single_indirect_block = free_block_list
+                .allocate()
+                .ok_or_else(|| String::from("Not enough free blocks"))?;
+            inode.block_pointers[INDIRECT_POINTER_INDEX] = single_indirect_block;
+            let mut single_entries = [0u32; POINTERS_PER_INDIRECT_BLOCK];
+            for entry in single_entries.iter_mut().take(single_indirect_count) {
+                *entry = free_block_list
+                    .allocate()
+                    .ok_or_else(|| String::from("Not enough free blocks"))?;
+            }
+            self.write_indirect_block(single_indirect_block, &single_entries);
+
+            let mut remaining = size - DIRECT_POINTERS - single_indirect_count;
+            if remaining == 0 {
+                return Ok(());
+            }
+
+            let double_indirect_block = free_block_list
+                .allocate()
+                .ok_or_else(|| String::from("Not enough free blocks"))?;
+            inode.block_pointers[DOUBLE_INDIRECT_POINTER_INDEX] = double_indirect_block;
+            let mut outer_entries = [0u32; POINTERS_PER_INDIRECT_BLOCK];
+            let mut outer_index = 0;
+            while remaining > 0 {
+                let inner_block = free_block_list
+                    .allocate()
+                    .ok_or_else(|| String::from("Not enough free blocks"))?;
+                outer_entries[outer_index] = inner_block;
+                let inner_count = remaining.min(POINTERS_PER_INDIRECT_BLOCK);
+                let mut inner_entries = [0u32; POINTERS_PER_INDIRECT_BLOCK];
+                for entry in inner_entries.iter_mut().take(inner_count) {
+                    *entry = free_block_list
+                        .allocate()
+                        .ok_or_else(|| String::from("Not enough free blocks"))?;
+                }
+                self.write_indirect_block(inner_block, &inner_entries);
+                remaining -= inner_count;
+                outer_index += 1;
+            }
+            self.write_indirect_block(double_indirect_block, &outer_entries);
+            Ok(())
+        }
+
+        /// Frees every block belonging to `inode`: the direct pointers, the single
+        /// indirect block and everything it points to, and the double indirect block
+        /// and everything *it* points to. Mirrors `allocate_blocks`'s tiering.
+        fn free_blocks(&mut self, inode: &IDXNode, free_block_list: &mut Bitmap) {
+            let direct_count = (inode.size as usize).min(DIRECT_POINTERS);
+            for &block in inode.block_pointers.iter().take(direct_count) {
+                free_block_list.deallocate(block as usize);
+            }
+            if inode.size as usize <= DIRECT_POINTERS {
+                return;
+            }
+
+            let single_indirect_block = inode.block_pointers[INDIRECT_POINTER_INDEX];
+            let single_indirect_count =
+                (inode.size as usize - DIRECT_POINTERS).min(POINTERS_PER_INDIRECT_BLOCK);
+            let single_entries = self.read_indirect_block(single_indirect_block);
+            for &block in single_entries.iter().take(single_indirect_count) {
+                free_block_list.deallocate(block as usize);
+            }
+            free_block_list.deallocate(single_indirect_block as usize);
+
+            let mut remaining = inode.size as usize - DIRECT_POINTERS - single_indirect_count;
+            if remaining == 0 {
+                return;
+            }
+
+            let double_indirect_block = inode.block_pointers[DOUBLE_INDIRECT_POINTER_INDEX];
+            let outer_entries = self.read_indirect_block(double_indirect_block);
+            let mut outer_index = 0;
+            while remaining > 0 {
+                let inner_block = outer_entries[outer_index];
+                let inner_count = remaining.min(POINTERS_PER_INDIRECT_BLOCK);
+                let inner_entries = self.read_indirect_block(inner_block);
+                for &block in inner_entries.iter().take(inner_count) {
+                    free_block_list.deallocate(block as usize);
+                }
+                free_block_list.deallocate(inner_block as usize);
+                remaining -= inner_count;
+                outer_index += 1;
+            }
+            free_block_list.deallocate(double_indirect_block as usize);
+        }
+
+        /// Reads an indirect block off disk and unpacks it into its `u32` block indices.
+        fn read_indirect_block(&mut self, indirect_block: u32) -> [u32; POINTERS_PER_INDIRECT_BLOCK] {
+            let mut raw = [0u8; BLOCK_SIZE];
+            self.disk
+                .seek(std::io::SeekFrom::Start(
+                    (FREE_BLOCK_SIZE + BLOCK_SIZE * indirect_block as usize) as u64,
+                ))
+                .unwrap();
+            self.disk.read_exact(&mut raw).unwrap();
+            let mut entries = [0u32; POINTERS_PER_INDIRECT_BLOCK];
+            for (i, entry) in entries.iter_mut().enumerate() {
+                *entry = u32::from_le_bytes(raw[i * 4..i * 4 + 4].try_into().unwrap());
+            }
+            entries
+        }
+
+        /// Packs `entries` into a block's worth of bytes and writes it out as the indirect block.
+        fn write_indirect_block(
+            &mut self,
+            indirect_block: u32,
+            entries: &[u32; POINTERS_PER_INDIRECT_BLOCK],
+        ) {
+            let mut raw = [0u8; BLOCK_SIZE];
+            for (i, entry) in entries.iter().enumerate() {
+                raw[i * 4..i * 4 + 4].copy_from_slice(&entry.to_le_bytes());
+            }
+            self.disk
+                .seek(std::io::SeekFrom::Start(
+                    (FREE_BLOCK_SIZE + BLOCK_SIZE * indirect_block as usize) as u64,
+                ))
+                .unwrap();
+            self.disk.write_all(&raw).unwrap();
         }
     }
 
     // Written by Jack Champagne
+
+    /// Mounts a `MyFileSystem` as a real directory via FUSE so it can be driven by
+    /// ordinary POSIX tools (`ls`, `cat`, `cp`, ...). Lookup/readdir/getattr walk the
+    /// real directory hierarchy (not just the root level), matching the hierarchical
+    /// filesystem this crate grew into. Gated behind the `fuse` feature since it
+    /// pulls in the `fuser` crate and only makes sense on platforms with a
+    /// FUSE driver available.
+    #[cfg(feature = "fuse")]
+    pub mod fuse_backend {
+        use super::{
+            inode_number, is_dir, FileAttr as Stat, FileDisk, MyFileSystem, BLOCK_SIZE,
+            MAX_INODES,
+        };
+        use fuser::{
+            FileAttr, FileType, Filesystem, MountOption, ReplyAttr, ReplyCreate, ReplyData,
+            ReplyDirectory, ReplyEmpty, ReplyEntry, Request,
+        };
+        use std::ffi::OsStr;
+        use std::fs::File;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        const TTL: Duration = Duration::from_secs(1);
+
+        fn to_system_time(seconds: u64) -> std::time::SystemTime {
+            UNIX_EPOCH + Duration::from_secs(seconds)
+        }
+
+        /// Inode numbers handed out to the kernel are just `1 + table index`, since
+        /// FUSE reserves inode 1 for the root and `MyFileSystem`'s table is flat.
+        fn ino_to_index(ino: u64) -> usize {
+            (ino - 1) as usize
+        }
+
+        fn index_to_ino(index: usize) -> u64 {
+            index as u64 + 1
+        }
+
+        /// Finds the directory inode that holds an entry for `index` and that entry's
+        /// name. Unlike directories (which store a `..` pointer), regular files and
+        /// symlinks don't carry a parent pointer of their own, so this scans every
+        /// directory inode's entries instead — fine at this table's size.
+        fn locate(fs: &mut MyFileSystem<FileDisk>, index: usize) -> Option<(usize, String)> {
+            let target = inode_number(index);
+            for candidate in 0..MAX_INODES {
+                let dir_inode = fs.get_inode(candidate);
+                if dir_inode.used == 0 || !is_dir(&dir_inode) {
+                    continue;
+                }
+                for entry in fs.read_dir_entries(&dir_inode) {
+                    let name = entry.name_str();
+                    if entry.inode == target && name != "." && name != ".." {
+                        return Some((candidate, name.to_string()));
+                    }
+                }
+            }
+            None
+        }
+
+        /// Full path of the inode at `index`, walking up to the root (inode 0, which
+        /// has no parent entry pointing at it) via `locate`.
+        fn full_path(fs: &mut MyFileSystem<FileDisk>, index: usize) -> String {
+            if index == 0 {
+                return "/".to_string();
+            }
+            let Some((parent_index, name)) = locate(fs, index) else {
+                return "/".to_string();
+            };
+            let parent_path = full_path(fs, parent_index);
+            if parent_path == "/" {
+                format!("/{}", name)
+            } else {
+                format!("{}/{}", parent_path, name)
+            }
+        }
+
+        /// Path a newly looked-up/created child name resolves to, given the parent
+        /// directory inode FUSE passed in.
+        fn child_path(fs: &mut MyFileSystem<FileDisk>, parent_ino: u64, name: &OsStr) -> String {
+            let parent_path = full_path(fs, ino_to_index(parent_ino));
+            let name = name.to_string_lossy();
+            if parent_path == "/" {
+                format!("/{}", name)
+            } else {
+                format!("{}/{}", parent_path, name)
+            }
+        }
+
+        fn file_attr(ino: u64, stat: &Stat, dir: bool) -> FileAttr {
+            FileAttr {
+                ino,
+                size: stat.size,
+                blocks: stat.size.div_ceil(BLOCK_SIZE as u64),
+                atime: to_system_time(stat.atime),
+                mtime: to_system_time(stat.mtime),
+                ctime: to_system_time(stat.ctime),
+                crtime: to_system_time(stat.ctime),
+                kind: if dir { FileType::Directory } else { FileType::RegularFile },
+                perm: (stat.mode & 0o7777) as u16,
+                nlink: stat.links,
+                uid: stat.uid,
+                gid: stat.gid,
+                rdev: 0,
+                blksize: BLOCK_SIZE as u32,
+                flags: 0,
+            }
+        }
+
+        impl Filesystem for MyFileSystem<FileDisk> {
+            fn lookup(&mut self, _req: &Request, parent: u64, name: &OsStr, reply: ReplyEntry) {
+                let path = child_path(self, parent, name);
+                match self.resolve_path(&path) {
+                    Ok((index, inode)) => {
+                        let stat = self.stat(&path).unwrap();
+                        reply.entry(&TTL, &file_attr(index_to_ino(index), &stat, is_dir(&inode)), 0);
+                    }
+                    Err(_) => reply.error(libc::ENOENT),
+                }
+            }
+
+            fn getattr(&mut self, _req: &Request, ino: u64, reply: ReplyAttr) {
+                let inode = self.get_inode(ino_to_index(ino));
+                if inode.used == 1 {
+                    let path = full_path(self, ino_to_index(ino));
+                    let stat = self.stat(&path).unwrap();
+                    reply.attr(&TTL, &file_attr(ino, &stat, is_dir(&inode)));
+                } else {
+                    reply.error(libc::ENOENT);
+                }
+            }
+
+            fn readdir(
+                &mut self,
+                _req: &Request,
+                ino: u64,
+                _fh: u64,
+                offset: i64,
+                mut reply: ReplyDirectory,
+            ) {
+                let path = full_path(self, ino_to_index(ino));
+                let Ok(entries) = self.readdir(&path) else {
+                    reply.ok();
+                    return;
+                };
+                for (i, entry) in entries.into_iter().enumerate().skip(offset as usize) {
+                    let child_index = ino_to_index(entry.inode as u64);
+                    let child_inode = self.get_inode(child_index);
+                    let kind = if is_dir(&child_inode) {
+                        FileType::Directory
+                    } else {
+                        FileType::RegularFile
+                    };
+                    if reply.add(index_to_ino(child_index), i as i64 + 1, kind, entry.name) {
+                        break;
+                    }
+                }
+                reply.ok();
+            }
+
+            fn read(
+                &mut self,
+                req: &Request,
+                ino: u64,
+                _fh: u64,
+                offset: i64,
+                _size: u32,
+                _flags: i32,
+                _lock_owner: Option<u64>,
+                reply: ReplyData,
+            ) {
+                let path = full_path(self, ino_to_index(ino));
+                let block_num = (offset as usize / BLOCK_SIZE) as u32;
+                match self.read(&path, block_num, req.uid(), req.gid()) {
+                    Ok(buf) => reply.data(&buf),
+                    Err(_) => reply.error(libc::EIO),
+                }
+            }
+
+            fn write(
+                &mut self,
+                req: &Request,
+                ino: u64,
+                _fh: u64,
+                offset: i64,
+                data: &[u8],
+                _write_flags: u32,
+                _flags: i32,
+                _lock_owner: Option<u64>,
+                reply: fuser::ReplyWrite,
+            ) {
+                let path = full_path(self, ino_to_index(ino));
+                let block_num = (offset as usize / BLOCK_SIZE) as u32;
+                let mut buf = [0u8; BLOCK_SIZE];
+                buf[..data.len().min(BLOCK_SIZE)].copy_from_slice(&data[..data.len().min(BLOCK_SIZE)]);
+                match self.write(&path, block_num, &buf, req.uid(), req.gid()) {
+                    Ok(()) => reply.written(data.len() as u32),
+                    Err(_) => reply.error(libc::EIO),
+                }
+            }
+
+            fn create(
+                &mut self,
+                req: &Request,
+                parent: u64,
+                name: &OsStr,
+                _mode: u32,
+                _umask: u32,
+                _flags: i32,
+                reply: ReplyCreate,
+            ) {
+                let path = child_path(self, parent, name);
+                match self.create_file(&path, 0, req.uid(), req.gid()) {
+                    Ok(()) => match self.resolve_path(&path) {
+                        Ok((index, inode)) => {
+                            let stat = self.stat(&path).unwrap();
+                            reply.created(
+                                &TTL,
+                                &file_attr(index_to_ino(index), &stat, is_dir(&inode)),
+                                0,
+                                0,
+                                0,
+                            );
+                        }
+                        Err(_) => reply.error(libc::EIO),
+                    },
+                    Err(_) => reply.error(libc::ENOSPC),
+                }
+            }
+
+            fn unlink(&mut self, _req: &Request, parent: u64, name: &OsStr, reply: ReplyEmpty) {
+                let path = child_path(self, parent, name);
+                match self.unlink(&path) {
+                    Ok(()) => reply.ok(),
+                    Err(_) => reply.error(libc::ENOENT),
+                }
+            }
+        }
+
+        /// Mounts the disk image `disk_name` at `mountpoint`, blocking the calling
+        /// thread until the filesystem is unmounted.
+        pub fn mount(disk_name: &str, mountpoint: &str) -> std::io::Result<()> {
+            let fs = MyFileSystem::new(disk_name);
+            fuser::mount2(fs, mountpoint, &[MountOption::FSName("myfs".to_string())])
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::myfs::*;
-    use std::process::{Command, Stdio};
-    fn setup() {
-        Command::new("./create_fs")
-            .arg("disk0")
-            .stdout(Stdio::null())
-            .spawn()
-            .expect("create_fs failed to run");
-    }
 
     #[test]
     #[should_panic]
     fn bad_fs() {
-        setup();
-        // Dummy filename in byte format
-        let filename = [0, 0, 0, 0, 0, 0, 0, 1];
         let mut my_fs = MyFileSystem::new("diskL");
-        my_fs.create_file(filename, 7).unwrap();
+        my_fs.create_file("/file1", 7, 0, 0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn bad_file() {
-        setup();
-        // Dummy filename in byte format
-        let filename = [0, 0, 0, 0, 0, 0, 0, 1];
-        let mut my_fs = MyFileSystem::new("disk0");
-        my_fs.create_file(filename, 100).unwrap();
+        let mut my_fs = MyFileSystem::new_in_memory();
+        my_fs.create_file("/file1", 200, 0, 0).unwrap();
     }
 
     #[test]
     fn good_file_ops() {
-        setup();
-        let mut my_fs = MyFileSystem::new("disk0");
-        // 'testfile' in byte format
-        let my_filename = [116, 101, 115, 116, 102, 105, 108, 101];
-        my_fs.create_file(my_filename, 8).unwrap();
-        my_fs.write(my_filename, 1, &[12; BLOCK_SIZE]).unwrap();
+        let mut my_fs = MyFileSystem::new_in_memory();
+        my_fs.create_file("/testfile", 8, 0, 0).unwrap();
+        my_fs.write("/testfile", 1, &[12; BLOCK_SIZE], 0, 0).unwrap();
         let buf = [0; BLOCK_SIZE];
-        assert!(my_fs.read(my_filename, 0).unwrap() == buf);
-        assert!(my_fs.read(my_filename, 1).unwrap() != buf);
+        assert!(my_fs.read("/testfile", 0, 0, 0).unwrap() == buf);
+        assert!(my_fs.read("/testfile", 1, 0, 0).unwrap() != buf);
     }
 }
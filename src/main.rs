@@ -27,56 +27,60 @@ fn main() {
     my_file_system.close_disk();
 }
 
-// Small helper function to turn a &str in to a [u8; 8] by taking the first 8 bytes and packing them
-fn get_filename_array(filename: &str) -> [u8; 8] {
-    let mut filename_array: [u8; 8] = [0; 8];
-    let bytes = filename.as_bytes();
-    for i in 0..bytes.len().min(8) {
-        filename_array[i] = bytes[i];
-    }
-    filename_array
-}
-
-fn do_file_op(my_fs: &mut myfs::MyFileSystem, line: &mut String) {
+fn do_file_op(my_fs: &mut myfs::MyFileSystem<myfs::FileDisk>, line: &mut String) {
     let mut split_parts = line.split_ascii_whitespace();
     let op = split_parts.next().unwrap();
     let args: Vec<&str> = split_parts.collect();
     match &op.chars().next().unwrap() {
         'C' => {
-            let filename = get_filename_array(args[0]);
+            let path = as_abs_path(args[0]);
             let size = args[1].parse().unwrap();
             my_fs
-                .create_file(filename, size)
+                .create_file(&path, size, 0, 0)
                 .expect("Creation of file failed");
         }
         'W' => {
-            let filename = get_filename_array(args[0]);
+            let path = as_abs_path(args[0]);
             let block_num = args[1].parse().unwrap();
             my_fs
-                .write(filename, block_num, &BUFF)
+                .write(&path, block_num, &BUFF, 0, 0)
                 .expect("Writing failed");
         }
         'L' => {
             my_fs.ls();
         }
         'R' => {
-            let filename = get_filename_array(args[0]);
+            let path = as_abs_path(args[0]);
             let block_num = args[1].parse().unwrap();
             println!(
                 "{}",
                 String::from_utf8_lossy(
                     &my_fs
-                        .read(filename, block_num)
+                        .read(&path, block_num, 0, 0)
                         .expect("Reading block failed")
                 )
             );
         }
         'D' => {
-            let filename = get_filename_array(args[0]);
-            my_fs.delete_file(filename).expect("Deleting file failed");
+            let path = as_abs_path(args[0]);
+            my_fs.unlink(&path).expect("Deleting file failed");
+        }
+        'M' => {
+            let path = as_abs_path(args[0]);
+            my_fs.mkdir(&path, 0, 0).expect("Creating directory failed");
         }
         _ => (),
     }
 }
 
+/// Resolves an instruction file's path argument against the root, so the legacy
+/// bare-filename format (`C file1 3`) keeps working alongside full paths (`C /file1 3`).
+fn as_abs_path(path: &str) -> String {
+    if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    }
+}
+
 // Written by Jack Champagne